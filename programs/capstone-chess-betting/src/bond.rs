@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::transfer;
+use anchor_lang::system_program::Transfer;
+
+use crate::error::ErrorCode;
+use crate::MatchConfig;
+use crate::OracleBond;
+
+/// Bond of a result proposer, None when the admin proposed. Oracles must always pass theirs.
+pub fn proposer_bond<'a, 'info>(
+    config: &MatchConfig,
+    proposer: &Pubkey,
+    bond: &'a mut Option<Account<'info, OracleBond>>,
+) -> Result<Option<&'a mut Account<'info, OracleBond>>> {
+    if *proposer == config.authority {
+        return Ok(None);
+    }
+
+    let bond = bond.as_mut().ok_or(ErrorCode::OracleNotBondedError)?;
+    require_keys_eq!(bond.oracle, *proposer, ErrorCode::OracleNotBondedError);
+
+    Ok(Some(bond))
+}
+
+/// Moves up to `amount` of the bond to `to` and returns what was actually taken.
+pub fn slash_oracle_bond<'info>(
+    bond: &mut Account<'info, OracleBond>,
+    bond_vault: &SystemAccount<'info>,
+    to: AccountInfo<'info>,
+    amount: u64,
+    system_program: &Program<'info, System>,
+) -> Result<u64> {
+    let amount = amount.min(bond.amount);
+    if amount == 0 {
+        return Ok(0);
+    }
+
+    let transfer_accounts = Transfer {
+        from: bond_vault.to_account_info(),
+        to,
+    };
+
+    let bond_key = bond.key();
+    let signer_seeds: &[&[&[u8]]; 1] = &[&[
+        b"oracle_bond_vault",
+        bond_key.as_ref(),
+        &[bond.vault_bump],
+    ]];
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        system_program.to_account_info(),
+        transfer_accounts,
+        signer_seeds,
+    );
+
+    transfer(cpi_ctx, amount)?;
+    bond.amount -= amount;
+
+    Ok(amount)
+}
@@ -0,0 +1,295 @@
+use anchor_lang::prelude::*;
+
+pub type Square = u8;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Color {
+    White,
+    Black,
+}
+
+impl Color {
+    pub fn opposite(self) -> Color {
+        match self {
+            Color::White => Color::Black,
+            Color::Black => Color::White,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[repr(u8)]
+pub enum PieceKind {
+    Pawn = 1,
+    Knight = 2,
+    Bishop = 3,
+    Rook = 4,
+    Queen = 5,
+    King = 6,
+}
+
+impl PieceKind {
+    pub fn from_u8(value: u8) -> Option<PieceKind> {
+        match value {
+            1 => Some(PieceKind::Pawn),
+            2 => Some(PieceKind::Knight),
+            3 => Some(PieceKind::Bishop),
+            4 => Some(PieceKind::Rook),
+            5 => Some(PieceKind::Queen),
+            6 => Some(PieceKind::King),
+            _ => None,
+        }
+    }
+}
+
+/// A square holds 0 when empty, otherwise the piece kind in the low 3 bits plus `BLACK` for black pieces.
+pub const EMPTY: u8 = 0;
+pub const BLACK: u8 = 8;
+
+pub const WHITE_KINGSIDE: u8 = 1;
+pub const WHITE_QUEENSIDE: u8 = 2;
+pub const BLACK_KINGSIDE: u8 = 4;
+pub const BLACK_QUEENSIDE: u8 = 8;
+
+pub fn piece(color: Color, kind: PieceKind) -> u8 {
+    match color {
+        Color::White => kind as u8,
+        Color::Black => kind as u8 | BLACK,
+    }
+}
+
+pub fn piece_kind(piece: u8) -> Option<PieceKind> {
+    PieceKind::from_u8(piece & 7)
+}
+
+pub fn piece_color(piece: u8) -> Color {
+    if piece & BLACK == 0 {
+        Color::White
+    } else {
+        Color::Black
+    }
+}
+
+pub fn file_of(square: Square) -> u8 {
+    square & 7
+}
+
+pub fn rank_of(square: Square) -> u8 {
+    square >> 3
+}
+
+pub fn square_at(file: u8, rank: u8) -> Square {
+    rank * 8 + file
+}
+
+/// Square `df` files and `dr` ranks away, if it is still on the board.
+pub fn offset(square: Square, df: i8, dr: i8) -> Option<Square> {
+    let file = file_of(square) as i8 + df;
+    let rank = rank_of(square) as i8 + dr;
+    if (0..8).contains(&file) && (0..8).contains(&rank) {
+        Some(square_at(file as u8, rank as u8))
+    } else {
+        None
+    }
+}
+
+/// Full position, squares indexed from a1 = 0 to h8 = 63.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Board {
+    pub squares: [u8; 64],
+    pub side_to_move: Color,
+    pub castling: u8,
+    pub en_passant: Option<Square>, // only set when a pawn could actually take en passant
+    pub halfmove_clock: u16,
+    pub fullmove_number: u16,
+}
+
+const BACK_RANK: [PieceKind; 8] = [
+    PieceKind::Rook,
+    PieceKind::Knight,
+    PieceKind::Bishop,
+    PieceKind::Queen,
+    PieceKind::King,
+    PieceKind::Bishop,
+    PieceKind::Knight,
+    PieceKind::Rook,
+];
+
+impl Board {
+    pub fn start_position() -> Board {
+        let mut squares = [EMPTY; 64];
+        for file in 0..8u8 {
+            squares[square_at(file, 0) as usize] = piece(Color::White, BACK_RANK[file as usize]);
+            squares[square_at(file, 1) as usize] = piece(Color::White, PieceKind::Pawn);
+            squares[square_at(file, 6) as usize] = piece(Color::Black, PieceKind::Pawn);
+            squares[square_at(file, 7) as usize] = piece(Color::Black, BACK_RANK[file as usize]);
+        }
+
+        Board {
+            squares,
+            side_to_move: Color::White,
+            castling: WHITE_KINGSIDE | WHITE_QUEENSIDE | BLACK_KINGSIDE | BLACK_QUEENSIDE,
+            en_passant: None,
+            halfmove_clock: 0,
+            fullmove_number: 1,
+        }
+    }
+
+    pub fn from_fen(fen: &str) -> Option<Board> {
+        let mut fields = fen.split_whitespace();
+        let placement = fields.next()?;
+        let side = fields.next()?;
+        let castling = fields.next().unwrap_or("-");
+        let en_passant = fields.next().unwrap_or("-");
+        let halfmove_clock = fields.next().unwrap_or("0").parse().ok()?;
+        let fullmove_number = fields.next().unwrap_or("1").parse().ok()?;
+
+        let mut squares = [EMPTY; 64];
+        let mut rank = 7i8;
+        let mut file = 0u8;
+        for c in placement.chars() {
+            match c {
+                '/' => {
+                    rank -= 1;
+                    file = 0;
+                }
+                '1'..='8' => file += c as u8 - b'0',
+                _ => {
+                    let kind = match c.to_ascii_lowercase() {
+                        'p' => PieceKind::Pawn,
+                        'n' => PieceKind::Knight,
+                        'b' => PieceKind::Bishop,
+                        'r' => PieceKind::Rook,
+                        'q' => PieceKind::Queen,
+                        'k' => PieceKind::King,
+                        _ => return None,
+                    };
+                    let color = if c.is_ascii_uppercase() { Color::White } else { Color::Black };
+                    if file > 7 || rank < 0 {
+                        return None;
+                    }
+                    squares[square_at(file, rank as u8) as usize] = piece(color, kind);
+                    file += 1;
+                }
+            }
+        }
+
+        let side_to_move = match side {
+            "w" => Color::White,
+            "b" => Color::Black,
+            _ => return None,
+        };
+
+        let mut rights = 0;
+        for c in castling.chars() {
+            rights |= match c {
+                'K' => WHITE_KINGSIDE,
+                'Q' => WHITE_QUEENSIDE,
+                'k' => BLACK_KINGSIDE,
+                'q' => BLACK_QUEENSIDE,
+                '-' => 0,
+                _ => return None,
+            };
+        }
+
+        Some(Board {
+            squares,
+            side_to_move,
+            castling: rights,
+            en_passant: parse_square(en_passant),
+            halfmove_clock,
+            fullmove_number,
+        })
+    }
+
+    pub fn piece_at(&self, square: Square) -> u8 {
+        self.squares[square as usize]
+    }
+
+    pub fn king_square(&self, color: Color) -> Option<Square> {
+        let king = piece(color, PieceKind::King);
+        self.squares.iter().position(|p| *p == king).map(|sq| sq as Square)
+    }
+
+    /// Neither side can mate: bare kings, a single minor piece, or bishops all on one colour.
+    pub fn is_insufficient_material(&self) -> bool {
+        let mut minors = 0;
+        let mut bishop_colors = [false; 2];
+        for (square, p) in self.squares.iter().enumerate() {
+            match piece_kind(*p) {
+                None | Some(PieceKind::King) => {}
+                Some(PieceKind::Knight) => minors += 1,
+                Some(PieceKind::Bishop) => {
+                    let sq = square as u8;
+                    bishop_colors[((file_of(sq) + rank_of(sq)) & 1) as usize] = true;
+                    minors += 1;
+                }
+                Some(_) => return false,
+            }
+        }
+        minors <= 1 || ((!bishop_colors[0] || !bishop_colors[1]) && self.only_bishops())
+    }
+
+    fn only_bishops(&self) -> bool {
+        self.squares
+            .iter()
+            .all(|p| matches!(piece_kind(*p), None | Some(PieceKind::King) | Some(PieceKind::Bishop)))
+    }
+
+    /// Nibble packed position for account storage.
+    pub fn pack(&self) -> CompactBoard {
+        let mut squares = [0u8; 32];
+        for (i, pair) in self.squares.chunks(2).enumerate() {
+            squares[i] = pair[0] | (pair[1] << 4);
+        }
+        let side = match self.side_to_move {
+            Color::White => 0,
+            Color::Black => 1,
+        };
+
+        CompactBoard {
+            squares,
+            flags: side | (self.castling << 1),
+            en_passant: self.en_passant.unwrap_or(NO_SQUARE),
+            halfmove_clock: self.halfmove_clock,
+            fullmove_number: self.fullmove_number,
+        }
+    }
+
+    pub fn unpack(compact: &CompactBoard) -> Board {
+        let mut squares = [EMPTY; 64];
+        for (i, byte) in compact.squares.iter().enumerate() {
+            squares[2 * i] = byte & 0x0f;
+            squares[2 * i + 1] = byte >> 4;
+        }
+
+        Board {
+            squares,
+            side_to_move: if compact.flags & 1 == 0 { Color::White } else { Color::Black },
+            castling: (compact.flags >> 1) & 0x0f,
+            en_passant: if compact.en_passant < 64 { Some(compact.en_passant) } else { None },
+            halfmove_clock: compact.halfmove_clock,
+            fullmove_number: compact.fullmove_number,
+        }
+    }
+}
+
+pub const NO_SQUARE: u8 = 0xff;
+
+/// 38 byte form of `Board` stored on chain.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub struct CompactBoard {
+    pub squares: [u8; 32], // two squares per byte, low nibble first
+    pub flags: u8,         // bit 0 side to move, bits 1-4 castling rights
+    pub en_passant: u8,    // NO_SQUARE when unset
+    pub halfmove_clock: u16,
+    pub fullmove_number: u16,
+}
+
+pub fn parse_square(name: &str) -> Option<Square> {
+    let bytes = name.as_bytes();
+    if bytes.len() != 2 || !(b'a'..=b'h').contains(&bytes[0]) || !(b'1'..=b'8').contains(&bytes[1]) {
+        return None;
+    }
+    Some(square_at(bytes[0] - b'a', bytes[1] - b'1'))
+}
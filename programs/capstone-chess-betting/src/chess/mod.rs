@@ -0,0 +1,11 @@
+//! Standard chess rules for validating games on chain.
+//!
+//! Everything works on a fixed 64 square mailbox and stack allocated move lists,
+//! so validating a move or detecting mate never touches the program heap.
+
+pub mod board;
+pub mod movegen;
+pub mod zobrist;
+
+pub use board::*;
+pub use movegen::*;
@@ -0,0 +1,417 @@
+use crate::chess::board::*;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Move {
+    pub from: Square,
+    pub to: Square,
+    pub promotion: Option<PieceKind>,
+}
+
+impl Move {
+    pub fn new(from: Square, to: Square, promotion: Option<PieceKind>) -> Move {
+        Move { from, to, promotion }
+    }
+
+    /// from in bits 0-5, to in bits 6-11, promotion piece kind (0 for none) in bits 12-14.
+    pub fn pack(&self) -> u16 {
+        let promotion = self.promotion.map_or(0, |kind| kind as u16);
+        self.from as u16 | (self.to as u16) << 6 | promotion << 12
+    }
+
+    pub fn unpack(packed: u16) -> Option<Move> {
+        let promotion = match (packed >> 12) & 7 {
+            0 => None,
+            kind @ 2..=5 => PieceKind::from_u8(kind as u8),
+            _ => return None,
+        };
+        Some(Move {
+            from: (packed & 63) as Square,
+            to: ((packed >> 6) & 63) as Square,
+            promotion,
+        })
+    }
+
+    /// Parses long algebraic notation such as "e2e4" or "e7e8q".
+    pub fn from_uci(uci: &str) -> Option<Move> {
+        if uci.len() != 4 && uci.len() != 5 {
+            return None;
+        }
+        let from = parse_square(uci.get(0..2)?)?;
+        let to = parse_square(uci.get(2..4)?)?;
+        let promotion = match uci.get(4..) {
+            Some("") => None,
+            Some("n") => Some(PieceKind::Knight),
+            Some("b") => Some(PieceKind::Bishop),
+            Some("r") => Some(PieceKind::Rook),
+            Some("q") => Some(PieceKind::Queen),
+            _ => return None,
+        };
+        Some(Move { from, to, promotion })
+    }
+}
+
+/// Upper bound on moves in any legal position is 218.
+pub const MAX_MOVES: usize = 256;
+
+pub struct MoveList {
+    moves: [Move; MAX_MOVES],
+    len: usize,
+}
+
+impl MoveList {
+    pub fn new() -> MoveList {
+        MoveList {
+            moves: [Move::new(0, 0, None); MAX_MOVES],
+            len: 0,
+        }
+    }
+
+    fn push(&mut self, mv: Move) {
+        self.moves[self.len] = mv;
+        self.len += 1;
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn as_slice(&self) -> &[Move] {
+        &self.moves[..self.len]
+    }
+
+    pub fn contains(&self, mv: &Move) -> bool {
+        self.as_slice().contains(mv)
+    }
+}
+
+impl Default for MoveList {
+    fn default() -> Self {
+        MoveList::new()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GameStatus {
+    Ongoing,
+    Checkmate { winner: Color },
+    Stalemate,
+    FiftyMoveRule,
+    ThreefoldRepetition,
+    InsufficientMaterial,
+}
+
+const KNIGHT_OFFSETS: [(i8, i8); 8] = [(1, 2), (2, 1), (2, -1), (1, -2), (-1, -2), (-2, -1), (-2, 1), (-1, 2)];
+const KING_OFFSETS: [(i8, i8); 8] = [(1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1), (0, -1), (1, -1)];
+const BISHOP_DIRECTIONS: [(i8, i8); 4] = [(1, 1), (-1, 1), (-1, -1), (1, -1)];
+const ROOK_DIRECTIONS: [(i8, i8); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+const PROMOTIONS: [PieceKind; 4] = [PieceKind::Queen, PieceKind::Rook, PieceKind::Bishop, PieceKind::Knight];
+
+impl Board {
+    pub fn is_square_attacked(&self, square: Square, by: Color) -> bool {
+        // a pawn attacking `square` sits one rank behind it from the attacker's point of view
+        let pawn_rank = match by {
+            Color::White => -1,
+            Color::Black => 1,
+        };
+        let pawn = piece(by, PieceKind::Pawn);
+        for df in [-1, 1] {
+            if offset(square, df, pawn_rank).is_some_and(|sq| self.piece_at(sq) == pawn) {
+                return true;
+            }
+        }
+
+        let knight = piece(by, PieceKind::Knight);
+        if KNIGHT_OFFSETS
+            .iter()
+            .any(|(df, dr)| offset(square, *df, *dr).is_some_and(|sq| self.piece_at(sq) == knight))
+        {
+            return true;
+        }
+
+        let king = piece(by, PieceKind::King);
+        if KING_OFFSETS
+            .iter()
+            .any(|(df, dr)| offset(square, *df, *dr).is_some_and(|sq| self.piece_at(sq) == king))
+        {
+            return true;
+        }
+
+        let queen = piece(by, PieceKind::Queen);
+        let bishop = piece(by, PieceKind::Bishop);
+        let rook = piece(by, PieceKind::Rook);
+        self.ray_hits(square, &BISHOP_DIRECTIONS, bishop, queen) || self.ray_hits(square, &ROOK_DIRECTIONS, rook, queen)
+    }
+
+    // First piece met along each direction is one of the two given pieces
+    fn ray_hits(&self, square: Square, directions: &[(i8, i8)], a: u8, b: u8) -> bool {
+        for (df, dr) in directions {
+            let mut current = square;
+            while let Some(next) = offset(current, *df, *dr) {
+                let p = self.piece_at(next);
+                if p != EMPTY {
+                    if p == a || p == b {
+                        return true;
+                    }
+                    break;
+                }
+                current = next;
+            }
+        }
+        false
+    }
+
+    pub fn in_check(&self) -> bool {
+        self.king_square(self.side_to_move)
+            .is_some_and(|king| self.is_square_attacked(king, self.side_to_move.opposite()))
+    }
+
+    /// Moves that follow piece movement rules but may leave the mover's own king in check.
+    pub fn pseudo_legal_moves(&self) -> MoveList {
+        let mut list = MoveList::new();
+        let us = self.side_to_move;
+
+        for from in 0..64u8 {
+            let p = self.piece_at(from);
+            if p == EMPTY || piece_color(p) != us {
+                continue;
+            }
+            match piece_kind(p) {
+                Some(PieceKind::Pawn) => self.pawn_moves(from, &mut list),
+                Some(PieceKind::Knight) => self.step_moves(from, &KNIGHT_OFFSETS, &mut list),
+                Some(PieceKind::Bishop) => self.slide_moves(from, &BISHOP_DIRECTIONS, &mut list),
+                Some(PieceKind::Rook) => self.slide_moves(from, &ROOK_DIRECTIONS, &mut list),
+                Some(PieceKind::Queen) => {
+                    self.slide_moves(from, &BISHOP_DIRECTIONS, &mut list);
+                    self.slide_moves(from, &ROOK_DIRECTIONS, &mut list);
+                }
+                Some(PieceKind::King) => {
+                    self.step_moves(from, &KING_OFFSETS, &mut list);
+                    self.castling_moves(from, &mut list);
+                }
+                None => {}
+            }
+        }
+
+        list
+    }
+
+    fn is_enemy(&self, square: Square) -> bool {
+        let p = self.piece_at(square);
+        p != EMPTY && piece_color(p) != self.side_to_move
+    }
+
+    fn pawn_moves(&self, from: Square, list: &mut MoveList) {
+        let (forward, start_rank, last_rank) = match self.side_to_move {
+            Color::White => (1, 1, 7),
+            Color::Black => (-1, 6, 0),
+        };
+
+        let push = |to: Square, list: &mut MoveList| {
+            if rank_of(to) == last_rank {
+                for kind in PROMOTIONS {
+                    list.push(Move::new(from, to, Some(kind)));
+                }
+            } else {
+                list.push(Move::new(from, to, None));
+            }
+        };
+
+        if let Some(one) = offset(from, 0, forward) {
+            if self.piece_at(one) == EMPTY {
+                push(one, list);
+                if rank_of(from) == start_rank {
+                    if let Some(two) = offset(one, 0, forward) {
+                        if self.piece_at(two) == EMPTY {
+                            list.push(Move::new(from, two, None));
+                        }
+                    }
+                }
+            }
+        }
+
+        for df in [-1, 1] {
+            if let Some(to) = offset(from, df, forward) {
+                if self.is_enemy(to) {
+                    push(to, list);
+                } else if self.en_passant == Some(to) {
+                    list.push(Move::new(from, to, None));
+                }
+            }
+        }
+    }
+
+    fn step_moves(&self, from: Square, offsets: &[(i8, i8)], list: &mut MoveList) {
+        for (df, dr) in offsets {
+            if let Some(to) = offset(from, *df, *dr) {
+                if self.piece_at(to) == EMPTY || self.is_enemy(to) {
+                    list.push(Move::new(from, to, None));
+                }
+            }
+        }
+    }
+
+    fn slide_moves(&self, from: Square, directions: &[(i8, i8)], list: &mut MoveList) {
+        for (df, dr) in directions {
+            let mut current = from;
+            while let Some(to) = offset(current, *df, *dr) {
+                if self.piece_at(to) == EMPTY {
+                    list.push(Move::new(from, to, None));
+                } else {
+                    if self.is_enemy(to) {
+                        list.push(Move::new(from, to, None));
+                    }
+                    break;
+                }
+                current = to;
+            }
+        }
+    }
+
+    fn castling_moves(&self, from: Square, list: &mut MoveList) {
+        let us = self.side_to_move;
+        let (rank, kingside, queenside) = match us {
+            Color::White => (0, WHITE_KINGSIDE, WHITE_QUEENSIDE),
+            Color::Black => (7, BLACK_KINGSIDE, BLACK_QUEENSIDE),
+        };
+        if from != square_at(4, rank) || self.castling & (kingside | queenside) == 0 {
+            return;
+        }
+        let them = us.opposite();
+        if self.is_square_attacked(from, them) {
+            return;
+        }
+
+        let rook = piece(us, PieceKind::Rook);
+        let empty = |files: &[u8]| files.iter().all(|f| self.piece_at(square_at(*f, rank)) == EMPTY);
+
+        // the destination square itself is covered by the usual own-king-in-check filter
+        if self.castling & kingside != 0
+            && self.piece_at(square_at(7, rank)) == rook
+            && empty(&[5, 6])
+            && !self.is_square_attacked(square_at(5, rank), them)
+        {
+            list.push(Move::new(from, square_at(6, rank), None));
+        }
+        if self.castling & queenside != 0
+            && self.piece_at(square_at(0, rank)) == rook
+            && empty(&[1, 2, 3])
+            && !self.is_square_attacked(square_at(3, rank), them)
+        {
+            list.push(Move::new(from, square_at(2, rank), None));
+        }
+    }
+
+    /// Plays a move from `pseudo_legal_moves` without checking king safety.
+    pub fn make_move(&mut self, mv: Move) {
+        let moving = self.piece_at(mv.from);
+        let captured = self.piece_at(mv.to);
+        let us = self.side_to_move;
+        let kind = piece_kind(moving);
+
+        if kind == Some(PieceKind::Pawn) || captured != EMPTY {
+            self.halfmove_clock = 0;
+        } else {
+            self.halfmove_clock = self.halfmove_clock.saturating_add(1);
+        }
+
+        if kind == Some(PieceKind::Pawn) && Some(mv.to) == self.en_passant && captured == EMPTY {
+            // the captured pawn sits beside the moving pawn, not on the destination square
+            self.squares[square_at(file_of(mv.to), rank_of(mv.from)) as usize] = EMPTY;
+        }
+
+        self.squares[mv.from as usize] = EMPTY;
+        self.squares[mv.to as usize] = match mv.promotion {
+            Some(promoted) => piece(us, promoted),
+            None => moving,
+        };
+
+        if kind == Some(PieceKind::King) && file_of(mv.from).abs_diff(file_of(mv.to)) == 2 {
+            let rank = rank_of(mv.from);
+            let (rook_from, rook_to) = if file_of(mv.to) == 6 { (7, 5) } else { (0, 3) };
+            self.squares[square_at(rook_to, rank) as usize] = self.squares[square_at(rook_from, rank) as usize];
+            self.squares[square_at(rook_from, rank) as usize] = EMPTY;
+        }
+
+        if kind == Some(PieceKind::King) {
+            self.castling &= match us {
+                Color::White => !(WHITE_KINGSIDE | WHITE_QUEENSIDE),
+                Color::Black => !(BLACK_KINGSIDE | BLACK_QUEENSIDE),
+            };
+        }
+        for (corner, right) in [(0, WHITE_QUEENSIDE), (7, WHITE_KINGSIDE), (56, BLACK_QUEENSIDE), (63, BLACK_KINGSIDE)] {
+            if mv.from == corner || mv.to == corner {
+                self.castling &= !right;
+            }
+        }
+
+        self.en_passant = None;
+        if kind == Some(PieceKind::Pawn) && rank_of(mv.from).abs_diff(rank_of(mv.to)) == 2 {
+            let enemy_pawn = piece(us.opposite(), PieceKind::Pawn);
+            let capturable = [-1, 1]
+                .iter()
+                .any(|df| offset(mv.to, *df, 0).is_some_and(|sq| self.piece_at(sq) == enemy_pawn));
+            if capturable {
+                self.en_passant = Some(square_at(file_of(mv.from), (rank_of(mv.from) + rank_of(mv.to)) / 2));
+            }
+        }
+
+        if us == Color::Black {
+            self.fullmove_number = self.fullmove_number.saturating_add(1);
+        }
+        self.side_to_move = us.opposite();
+    }
+
+    // The side that just moved must not be left in check
+    fn leaves_king_safe(&self, mv: Move) -> bool {
+        let mut next = *self;
+        next.make_move(mv);
+        next.king_square(self.side_to_move)
+            .is_some_and(|king| !next.is_square_attacked(king, next.side_to_move))
+    }
+
+    pub fn legal_moves(&self) -> MoveList {
+        let mut legal = MoveList::new();
+        for mv in self.pseudo_legal_moves().as_slice() {
+            if self.leaves_king_safe(*mv) {
+                legal.push(*mv);
+            }
+        }
+        legal
+    }
+
+    pub fn has_legal_move(&self) -> bool {
+        self.pseudo_legal_moves()
+            .as_slice()
+            .iter()
+            .any(|mv| self.leaves_king_safe(*mv))
+    }
+
+    pub fn is_legal(&self, mv: Move) -> bool {
+        self.pseudo_legal_moves().contains(&mv) && self.leaves_king_safe(mv)
+    }
+
+    /// Result of the position, `history` holding the hashes of earlier positions with the same side to move or not.
+    pub fn status(&self, history: &[u64]) -> GameStatus {
+        if !self.has_legal_move() {
+            if self.in_check() {
+                return GameStatus::Checkmate { winner: self.side_to_move.opposite() };
+            }
+            return GameStatus::Stalemate;
+        }
+        if self.halfmove_clock >= 100 {
+            return GameStatus::FiftyMoveRule;
+        }
+        let hash = self.hash();
+        if history.iter().filter(|h| **h == hash).count() >= 2 {
+            return GameStatus::ThreefoldRepetition;
+        }
+        if self.is_insufficient_material() {
+            return GameStatus::InsufficientMaterial;
+        }
+        GameStatus::Ongoing
+    }
+}
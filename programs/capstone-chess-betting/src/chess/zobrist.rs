@@ -0,0 +1,53 @@
+use crate::chess::board::*;
+
+const PIECE_KEYS: usize = 12 * 64;
+const SIDE_KEY: usize = PIECE_KEYS;
+const CASTLING_KEYS: usize = SIDE_KEY + 1;
+const EN_PASSANT_KEYS: usize = CASTLING_KEYS + 4;
+const KEY_COUNT: usize = EN_PASSANT_KEYS + 8;
+
+// splitmix64, so the table is fixed at compile time and identical off chain
+const fn generate_keys() -> [u64; KEY_COUNT] {
+    let mut keys = [0u64; KEY_COUNT];
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut i = 0;
+    while i < KEY_COUNT {
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        keys[i] = z ^ (z >> 31);
+        i += 1;
+    }
+    keys
+}
+
+static ZOBRIST_KEYS: [u64; KEY_COUNT] = generate_keys();
+
+impl Board {
+    /// Zobrist hash of everything that makes two positions the same for repetition purposes.
+    pub fn hash(&self) -> u64 {
+        let mut hash = 0u64;
+        for (square, p) in self.squares.iter().enumerate() {
+            if let Some(kind) = piece_kind(*p) {
+                let color = match piece_color(*p) {
+                    Color::White => 0,
+                    Color::Black => 6,
+                };
+                hash ^= ZOBRIST_KEYS[(color + kind as usize - 1) * 64 + square];
+            }
+        }
+        if self.side_to_move == Color::Black {
+            hash ^= ZOBRIST_KEYS[SIDE_KEY];
+        }
+        for bit in 0..4 {
+            if self.castling & (1 << bit) != 0 {
+                hash ^= ZOBRIST_KEYS[CASTLING_KEYS + bit];
+            }
+        }
+        if let Some(square) = self.en_passant {
+            hash ^= ZOBRIST_KEYS[EN_PASSANT_KEYS + file_of(square) as usize];
+        }
+        hash
+    }
+}
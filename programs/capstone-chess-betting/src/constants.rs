@@ -2,3 +2,84 @@ use anchor_lang::prelude::*;
 
 #[constant]
 pub const SEED: &str = "anchor";
+
+#[constant]
+pub const DEFAULT_SIDE_POOL_FEE_BPS: u16 = 200; // 2% of the side pool goes to the treasury
+
+#[constant]
+pub const DEFAULT_SIDE_BET_CUTOFF: i64 = 300; // side bets close 5 minutes after the match is accepted
+
+#[constant]
+pub const DEFAULT_MIN_BASE_SECONDS: u32 = 15;
+
+#[constant]
+pub const DEFAULT_MAX_BASE_SECONDS: u32 = 3 * 60 * 60;
+
+#[constant]
+pub const DEFAULT_MAX_INCREMENT_SECONDS: u32 = 180;
+
+#[constant]
+pub const DEFAULT_MAX_DELAY_SECONDS: u32 = 180;
+
+#[constant]
+pub const DEFAULT_REVEAL_WINDOW: i64 = 10 * 60;
+
+#[constant]
+pub const DEFAULT_CHALLENGE_WINDOW: i64 = 10 * 60; // time to answer a channel settlement with a newer signed state
+
+#[constant]
+pub const DEFAULT_DISPUTE_WINDOW: i64 = 60 * 60; // time players have to dispute a proposed result
+
+#[constant]
+pub const DEFAULT_DISPUTE_BOND: u64 = 100_000_000; // 0.1 SOL
+
+#[constant]
+pub const DEFAULT_MIN_ORACLE_BOND: u64 = 1_000_000_000; // 1 SOL
+
+#[constant]
+pub const DEFAULT_ORACLE_UNBOND_COOLDOWN: i64 = 7 * 24 * 60 * 60;
+
+#[constant]
+pub const DEFAULT_ORACLE_SLASH_BPS: u16 = 2500; // a quarter of the bond per overturned result
+
+#[constant]
+pub const DEFAULT_WITHDRAWAL_DELAY: i64 = 2 * 24 * 60 * 60;
+
+#[constant]
+pub const STATS_SHARDS: u8 = 16;
+
+#[constant]
+pub const MIN_WITHDRAWAL_DELAY: i64 = 24 * 60 * 60; // a compromised admin can't make withdrawals instant
+
+#[constant]
+pub const DEFAULT_WITHDRAWAL_EPOCH: i64 = 7 * 24 * 60 * 60;
+
+#[constant]
+pub const DEFAULT_WITHDRAWAL_EPOCH_CAP: u64 = 100_000_000_000; // 100 SOL
+
+#[constant]
+pub const DEFAULT_REFERRAL_FEE_BPS: u16 = 1000; // 10% of the fee each referred player pays
+
+#[constant]
+pub const DEFAULT_MIN_BET: u64 = 10_000_000; // 0.01 SOL
+
+#[constant]
+pub const DEFAULT_MAX_BET: u64 = 1_000_000_000_000; // 1000 SOL
+
+#[constant]
+pub const DEFAULT_CANCEL_PENALTY_BPS: u16 = 100; // 1% of the canceller's stake
+
+#[constant]
+pub const DEFAULT_CANCEL_PENALTY_STEP: i64 = 10 * 60;
+
+#[constant]
+pub const DEFAULT_CANCEL_PENALTY_STEP_BPS: u16 = 100;
+
+#[constant]
+pub const DEFAULT_MAX_CANCEL_PENALTY_BPS: u16 = 500;
+
+#[constant]
+pub const DEFAULT_CANCEL_OPPONENT_SHARE_BPS: u16 = 7500;
+
+// bullet, blitz, rapid, classical
+pub const DEFAULT_CATEGORY_TIMEOUTS: [i64; 4] = [10 * 60, 30 * 60, 60 * 60, 3 * 60 * 60];
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+
+use crate::error::ErrorCode;
+
+const SIGNATURE_OFFSETS_START: usize = 2; // after the signature count and a padding byte
+const SIGNATURE_OFFSETS_SIZE: usize = 14;
+const PUBKEY_SIZE: usize = 32;
+
+/// Succeeds if an earlier Ed25519 program instruction in this transaction verified `message` signed by `signer`.
+/// The precompile has already checked the signature, here we only confirm it covered the bytes we expect.
+pub fn verify_signature(instructions: &AccountInfo, signer: &Pubkey, message: &[u8]) -> Result<()> {
+    let current = load_current_index_checked(instructions)? as usize;
+
+    for index in 0..current {
+        let ix = load_instruction_at_checked(index, instructions)?;
+        if ix.program_id != ed25519_program::ID {
+            continue;
+        }
+        if signs(&ix.data, signer, message) {
+            return Ok(());
+        }
+    }
+
+    err!(ErrorCode::MissingSignatureError)
+}
+
+fn signs(data: &[u8], signer: &Pubkey, message: &[u8]) -> bool {
+    let Some(&count) = data.first() else {
+        return false;
+    };
+
+    (0..count as usize).any(|i| {
+        let start = SIGNATURE_OFFSETS_START + i * SIGNATURE_OFFSETS_SIZE;
+        let Some(offsets) = data.get(start..start + SIGNATURE_OFFSETS_SIZE) else {
+            return false;
+        };
+        let field = |n: usize| u16::from_le_bytes([offsets[2 * n], offsets[2 * n + 1]]);
+
+        // every part must live in the Ed25519 instruction itself, not point into some other instruction
+        let own_data = [1, 3, 6].iter().all(|&n| field(n) == u16::MAX);
+        let pubkey_offset = field(2) as usize;
+        let message_offset = field(4) as usize;
+        let message_size = field(5) as usize;
+
+        own_data
+            && data.get(pubkey_offset..pubkey_offset + PUBKEY_SIZE) == Some(signer.as_ref())
+            && data.get(message_offset..message_offset + message_size) == Some(message)
+    })
+}
@@ -25,4 +25,196 @@ pub enum ErrorCode {
 
     #[msg("Admin authority is not present or is not correct")]
     InvalidAdminError,
+
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+
+    #[msg("Fee must be expressed in basis points, at most 10000")]
+    InvalidFeeBps,
+
+    #[msg("Config value is out of range")]
+    InvalidConfigError,
+
+    #[msg("Side pool doesn't belong to this match or is in the wrong state")]
+    InvalidSidePool,
+
+    #[msg("Side betting is closed for this match")]
+    SidePoolClosed,
+
+    #[msg("Side pool hasn't been settled yet")]
+    SidePoolNotSettled,
+
+    #[msg("Players can't side bet on their own match")]
+    PlayerSideBetError,
+
+    #[msg("Queue tickets must have the same bet amount and time control")]
+    IncompatibleTicketsError,
+
+    #[msg("Time control is outside the configured bounds")]
+    InvalidTimeControlError,
+
+    #[msg("Match variant doesn't match the expected variant")]
+    VariantMismatchError,
+
+    #[msg("SlotHashes sysvar data is malformed")]
+    InvalidSlotHashesError,
+
+    #[msg("Revealed secret doesn't match player A's commitment")]
+    InvalidRevealError,
+
+    #[msg("Colours have already been assigned")]
+    ColorsAlreadyAssignedError,
+
+    #[msg("Player A still has time to reveal")]
+    RevealWindowOpenError,
+
+    #[msg("Move isn't legal in the current position")]
+    IllegalMoveError,
+
+    #[msg("It isn't this player's turn")]
+    NotYourTurnError,
+
+    #[msg("Game log is full")]
+    GameLogFullError,
+
+    #[msg("Game is already over on the board")]
+    GameFinishedError,
+
+    #[msg("Game hasn't ended on the board")]
+    GameNotFinishedError,
+
+    #[msg("On-chain move validation only supports standard chess")]
+    UnsupportedVariantError,
+
+    #[msg("Colours haven't been assigned yet")]
+    ColorsNotAssignedError,
+
+    #[msg("Game log doesn't belong to this match or is missing")]
+    InvalidGameLogError,
+
+    #[msg("Settlement contradicts the result recorded in the game log")]
+    GameLogResultMismatchError,
+
+    #[msg("Player has run out of time")]
+    FlagFallenError,
+
+    #[msg("Player to move still has time on the clock")]
+    FlagNotFallenError,
+
+    #[msg("Expected Ed25519 signature not found in this transaction")]
+    MissingSignatureError,
+
+    #[msg("Channel state doesn't belong to this match")]
+    InvalidChannelStateError,
+
+    #[msg("Channel state isn't newer than the one already submitted")]
+    StaleChannelStateError,
+
+    #[msg("Challenge window has closed")]
+    ChallengeWindowClosedError,
+
+    #[msg("Challenge window is still open")]
+    ChallengeWindowOpenError,
+
+    #[msg("Latest channel state has no result yet")]
+    ChannelGameOngoingError,
+
+    #[msg("Signer isn't a registered oracle")]
+    UnknownOracleError,
+
+    #[msg("Attestation nonce doesn't match this match record")]
+    InvalidNonceError,
+
+    #[msg("Player has already reported a result")]
+    ResultAlreadyReportedError,
+
+    #[msg("Result has already been disputed")]
+    AlreadyDisputedError,
+
+    #[msg("Proposed result is under dispute")]
+    ResultDisputedError,
+
+    #[msg("Arbiter set needs unique members and a strict majority threshold")]
+    InvalidArbiterSetError,
+
+    #[msg("Signer isn't on this dispute's panel")]
+    NotAnArbiterError,
+
+    #[msg("Arbiter has already voted on this dispute")]
+    AlreadyVotedError,
+
+    #[msg("Dispute is with the arbiter panel")]
+    DisputeEscalatedError,
+
+    #[msg("Result hasn't been disputed")]
+    NotDisputedError,
+
+    #[msg("Voting window is still open")]
+    VoteWindowOpenError,
+
+    #[msg("Voting window has closed")]
+    VoteWindowClosedError,
+
+    #[msg("Oracle has no active bond")]
+    OracleNotBondedError,
+
+    #[msg("Oracle bond is already unbonding")]
+    AlreadyUnbondingError,
+
+    #[msg("Oracle bond can't be withdrawn yet")]
+    UnbondCooldownError,
+
+    #[msg("Destination isn't on the treasury withdrawal allowlist")]
+    InvalidDestinationError,
+
+    #[msg("Withdrawal is still time-locked")]
+    WithdrawalLockedError,
+
+    #[msg("Withdrawal would exceed this epoch's cap")]
+    WithdrawalCapError,
+
+    #[msg("Admin signer set or threshold is invalid")]
+    InvalidSignerSetError,
+
+    #[msg("Signer is not in the admin signer set")]
+    NotAnAdminSignerError,
+
+    #[msg("Signer already approved this proposal")]
+    AlreadyApprovedError,
+
+    #[msg("Proposal does not have enough approvals")]
+    ThresholdNotMetError,
+
+    #[msg("Proposal instruction or accounts are invalid")]
+    InvalidProposalError,
+
+    #[msg("Signer does not hold the role this instruction needs")]
+    MissingRoleError,
+
+    #[msg("New matches are paused")]
+    PausedError,
+
+    #[msg("Fee split beneficiaries are invalid")]
+    InvalidFeeSplitError,
+
+    #[msg("Referrer is missing, unregistered or doesn't match the player's referral")]
+    InvalidReferralError,
+
+    #[msg("Bet amount is outside the configured minimum and maximum")]
+    BetOutOfRangeError,
+
+    #[msg("Channel state already has a result, settle it instead")]
+    ChannelGameFinishedError,
+
+    #[msg("Game is being played on chain, it can only end on the board or on the clock")]
+    GameOnChainError,
+
+    #[msg("Oracles can't settle this match directly, propose the result instead")]
+    InstantSettlementError,
+
+    #[msg("Dispute was already escalated once")]
+    EscalationUsedError,
+
+    #[msg("Dispute bond can't cover the arbiter panel's fees")]
+    ArbiterFeeError,
 }
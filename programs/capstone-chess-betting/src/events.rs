@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::ResultReason;
+use crate::Status;
+
+/// Emitted by every settlement path once the stakes have been paid out.
+#[event]
+pub struct MatchSettled {
+    pub match_account: Pubkey,
+    pub winner: Option<Pubkey>,
+    pub status: Status,
+    pub reason: ResultReason,
+    pub bet_amount: u64,
+}
+
+#[event]
+pub struct TreasuryWithdrawn {
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub total_withdrawn: u64,
+}
+
+#[event]
+pub struct FeesDistributed {
+    pub amount: u64,
+    pub total_distributed: u64,
+}
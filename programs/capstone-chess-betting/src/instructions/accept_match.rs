@@ -3,7 +3,11 @@ use anchor_lang::system_program::transfer;
 use anchor_lang::system_program::Transfer;
 
 use crate::MatchState;
+use crate::ProtocolStats;
+use crate::Referral;
+use crate::ReferrerRewards;
 use crate::Status::*;
+use crate::Variant;
 
 use crate::error::ErrorCode;
 
@@ -30,15 +34,48 @@ pub struct AcceptMatch<'info> {
     )]
     pub vault: SystemAccount<'info>,
 
+    #[account(
+        init_if_needed,
+        payer = player_b,
+        space = 8 + Referral::INIT_SPACE,
+        seeds = [b"referral", player_b.key().as_ref()],
+        bump,
+    )]
+    pub referral: Option<Account<'info, Referral>>, // only needed when the player has or names a referrer
+
+    #[account(
+        seeds = [b"referrer_rewards", referrer_rewards.referrer.as_ref()],
+        bump = referrer_rewards.bump,
+    )]
+    pub referrer_rewards: Option<Account<'info, ReferrerRewards>>,
+
+    #[account(
+        mut,
+        seeds = [b"stats", [stats.shard].as_ref()],
+        bump = stats.bump,
+    )]
+    pub stats: Account<'info, ProtocolStats>,
+
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> AcceptMatch<'info> {
-    pub fn accept_match(&mut self, _code: String) -> Result<()> {
+    pub fn accept_match(
+        &mut self,
+        _code: String,
+        expected_variant: Variant,
+        player_b_random: [u8; 32],
+        referrer: Option<Pubkey>,
+        bumps: &AcceptMatchBumps,
+    ) -> Result<()> {
         require!(
             self.match_account.status == Waiting,
             ErrorCode::InvalidMatchError
         );
+        require!(
+            self.match_account.variant == expected_variant,
+            ErrorCode::VariantMismatchError
+        );
         require_neq!(
             self.match_account.player_a.key().to_string(),
             self.player_b.key().to_string(),
@@ -50,6 +87,16 @@ impl<'info> AcceptMatch<'info> {
         );
 
         self.match_account.player_b = Some(self.player_b.key());
+        self.match_account.referrer_b = Referral::record(
+            &mut self.referral,
+            &self.referrer_rewards,
+            self.player_b.key(),
+            referrer,
+            bumps.referral,
+        )?;
+        self.match_account.accepted_at = Clock::get()?.unix_timestamp;
+        self.match_account.reveal_window_start = self.match_account.accepted_at;
+        self.match_account.player_b_random = player_b_random;
 
         let transfer_accounts = Transfer {
             from: self.player_b.to_account_info(),
@@ -59,6 +106,8 @@ impl<'info> AcceptMatch<'info> {
         let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), transfer_accounts);
 
         transfer(cpi_ctx, self.match_account.bet_amount)?;
+        ProtocolStats::add(&mut self.stats.matches_accepted, 1);
+        ProtocolStats::add(&mut self.stats.total_wagered, self.match_account.bet_amount);
 
         self.match_account.status = InProgress;
 
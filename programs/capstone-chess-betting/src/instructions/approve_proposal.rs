@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::MatchConfig;
+use crate::Proposal;
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct ApproveProposal<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump,
+        constraint = config.admin_signers.contains(&signer.key()) @ ErrorCode::NotAnAdminSignerError
+    )]
+    pub config: Account<'info, MatchConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"admin_proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+impl<'info> ApproveProposal<'info> {
+    pub fn approve_proposal(&mut self) -> Result<()> {
+        let signer = self.signer.key();
+        require!(
+            !self.proposal.approvals.contains(&signer),
+            ErrorCode::AlreadyApprovedError
+        );
+
+        // a removed signer's approval can leave a stale slot, drop those before taking a new one
+        let signers = &self.config.admin_signers;
+        self.proposal.approvals.retain(|key| signers.contains(key));
+        self.proposal.approvals.push(signer);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::transfer;
+use anchor_lang::system_program::Transfer;
+
+use crate::MatchConfig;
+use crate::OracleBond;
+use crate::Role;
+use crate::RoleAssignment;
+
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct BondOracle<'info> {
+    #[account(mut)]
+    pub oracle: Signer<'info>,
+
+    #[account(
+        init,
+        payer = oracle,
+        space = 8 + OracleBond::INIT_SPACE,
+        seeds = [b"oracle_bond", oracle.key().as_ref()],
+        bump,
+    )]
+    pub oracle_bond: Account<'info, OracleBond>,
+
+    #[account(
+        mut,
+        seeds = [b"oracle_bond_vault", oracle_bond.key().as_ref()],
+        bump,
+    )]
+    pub bond_vault: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump
+    )]
+    pub config: Account<'info, MatchConfig>,
+
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> BondOracle<'info> {
+    pub fn bond_oracle(&mut self, amount: u64, bumps: &BondOracleBumps) -> Result<()> {
+        require!(
+            self.config.has_role(Role::Oracle, &self.oracle.key(), &self.role_assignment),
+            ErrorCode::UnknownOracleError
+        );
+        require!(
+            amount >= self.config.min_oracle_bond,
+            ErrorCode::InsufficientBalance
+        );
+
+        // the vault keeps its own rent so slashing can take any part of `amount`
+        let rent_min = Rent::get()?.minimum_balance(0);
+        let transfer_accounts = Transfer {
+            from: self.oracle.to_account_info(),
+            to: self.bond_vault.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), transfer_accounts);
+        transfer(cpi_ctx, amount.checked_add(rent_min).ok_or(ErrorCode::MathOverflow)?)?;
+
+        self.oracle_bond.set_inner(OracleBond {
+            oracle: self.oracle.key(),
+            amount,
+            unbond_requested_at: 0,
+            open_proposals: 0,
+            bump: bumps.oracle_bond,
+            vault_bump: bumps.bond_vault,
+        });
+
+        Ok(())
+    }
+}
@@ -2,8 +2,12 @@ use anchor_lang::prelude::*;
 use anchor_lang::system_program::transfer;
 use anchor_lang::system_program::Transfer;
 
+use crate::GameLog;
 use crate::MatchState;
+use crate::ProtocolStats;
 use crate::MatchConfig;
+use crate::ReferrerRewards;
+use crate::SidePool;
 use crate::Status;
 
 use crate::error::ErrorCode;
@@ -24,10 +28,14 @@ pub struct CancelMatch<'info> {
         mut,
         seeds = [b"match", match_account.seed.to_le_bytes().as_ref(), code.as_bytes(), match_account.player_a.key().as_ref()],
         bump = match_account.bump,
-        close = treasury_pda
+        has_one = payer,
+        close = payer
     )]
     pub match_account: Account<'info, MatchState>,
 
+    #[account(mut)]
+    pub payer: SystemAccount<'info>,
+
     #[account(
         mut,
         seeds = [b"vault", match_account.key().as_ref()],
@@ -35,6 +43,12 @@ pub struct CancelMatch<'info> {
     )]
     pub vault: SystemAccount<'info>,
 
+    #[account(
+        seeds = [b"game_log", match_account.key().as_ref()],
+        bump = game_log.bump,
+    )]
+    pub game_log: Option<Account<'info, GameLog>>,
+
     #[account(
         mut,
         seeds = [b"config"],
@@ -49,12 +63,35 @@ pub struct CancelMatch<'info> {
     )]
     pub treasury_pda: SystemAccount<'info>,
 
+    #[account(
+        mut,
+        seeds = [b"side_pool", match_account.key().as_ref()],
+        bump = side_pool.bump,
+    )]
+    pub side_pool: Option<Account<'info, SidePool>>,
+
+    // the cancelling player's referrer, needed when they have one and pay a penalty
+    #[account(
+        mut,
+        seeds = [b"referrer_rewards", referrer_rewards.referrer.as_ref()],
+        bump = referrer_rewards.bump,
+    )]
+    pub referrer_rewards: Option<Account<'info, ReferrerRewards>>,
+
+    #[account(
+        mut,
+        seeds = [b"stats", [stats.shard].as_ref()],
+        bump = stats.bump,
+    )]
+    pub stats: Account<'info, ProtocolStats>,
+
     pub system_program: Program<'info, System>, // we need it anyways for the cpi(since we are transferring native sols)
 }
 
 impl<'info> CancelMatch<'info> {
     pub fn cancel_match(&mut self, _code: String) -> Result<()> {
         let match_state = self.match_account.status;
+        let mut kept_penalty = 0; // penalty left in the vault once the referrer is paid
 
         match match_state {
             Status::Waiting => {
@@ -89,6 +126,29 @@ impl<'info> CancelMatch<'info> {
                 transfer(cpi_ctx, self.vault.lamports())?;
             }
             Status::InProgress => {
+                // a game played on chain ends on the board or on the clock, see settle_game and claim_flag
+                if self.match_account.has_game_log {
+                    let game_log = self.game_log.as_ref().ok_or(ErrorCode::InvalidGameLogError)?;
+                    require!(!game_log.is_finished(), ErrorCode::GameFinishedError);
+                    return err!(ErrorCode::GameOnChainError);
+                }
+                // so does one a player has already reported a result for, see report_result
+                require!(
+                    self.match_account.reported_a.is_none() && self.match_account.reported_b.is_none(),
+                    ErrorCode::ResultAlreadyReportedError
+                );
+                // the opponent's compensation has to reach the actual opponent
+                require_keys_eq!(
+                    self.player_a.key(),
+                    self.match_account.player_a,
+                    ErrorCode::InvalidPlayerError
+                );
+                require_keys_eq!(
+                    self.player_b.key(),
+                    self.match_account.player_b.ok_or(ErrorCode::InvalidMatchError)?,
+                    ErrorCode::InvalidPlayerError
+                );
+
                 let req_balance = self.match_account.bet_amount.checked_mul(2);
                 require_eq!(
                     self.vault.lamports().to_string(),
@@ -98,6 +158,17 @@ impl<'info> CancelMatch<'info> {
                 let actor = self.player.key();
                 let player_a = self.match_account.player_a.key();
                 let player_b = self.match_account.player_b.unwrap().key();
+
+                // Once the game has overrun its time control nobody is abandoning it, so no penalty
+                // otherwise it grows with how long the game has run, and mostly goes to the opponent
+                let original_amount = self.match_account.bet_amount;
+                let now = Clock::get()?.unix_timestamp;
+                let (compensation, protocol_penalty) = if now > self.match_account.timeout_deadline(&self.config)? {
+                    (0, 0)
+                } else {
+                    self.config.cancel_penalty(original_amount, now - self.match_account.accepted_at)
+                };
+                let penalty = compensation + protocol_penalty;
                 match (actor == player_a, actor == player_b) {
                     (true, false) => {
                         let transfer_accounts_1 = Transfer {
@@ -127,10 +198,9 @@ impl<'info> CancelMatch<'info> {
                             signer_seeds,
                         );
 
-                        let original_amount = self.match_account.bet_amount;
-                        let final_amount_to_player = original_amount.checked_sub(original_amount.checked_div(100).unwrap());
+                        let final_amount_to_player = original_amount.checked_sub(penalty);
 
-                        transfer(cpi_ctx_2, original_amount)?;
+                        transfer(cpi_ctx_2, original_amount + compensation)?;
                         transfer(cpi_ctx_1, final_amount_to_player.unwrap())?;
                     }
                     (false, true) => {
@@ -161,18 +231,24 @@ impl<'info> CancelMatch<'info> {
                             signer_seeds,
                         );
 
-                        let original_amount = self.match_account.bet_amount;
-                        let final_amount_to_player = original_amount.checked_sub(original_amount.checked_div(100).unwrap());
+                        let final_amount_to_player = original_amount.checked_sub(penalty);
 
-                        transfer(cpi_ctx_2, original_amount)?;
+                        transfer(cpi_ctx_2, original_amount + compensation)?;
                         transfer(cpi_ctx_1, final_amount_to_player.unwrap())?;
                     }
                     (false, false) | (true, true) => {
                         return err!(ErrorCode::InvalidPlayerError);
                     }
                 }
+                kept_penalty = protocol_penalty - self.pay_referrer(actor, protocol_penalty)?;
+                ProtocolStats::add(&mut self.stats.cancel_penalties, kept_penalty);
+                ProtocolStats::add(&mut self.stats.cancel_compensation, compensation);
             }
-            Status::Completed | Status::Draw => {
+            Status::Completed
+            | Status::Draw
+            | Status::Disputed
+            | Status::Proposed
+            | Status::ChannelPending => {
                 return err!(ErrorCode::InvalidMatchError);
             }
         }
@@ -200,7 +276,61 @@ impl<'info> CancelMatch<'info> {
             // It’s fine to transfer the full balance for a 0-space SystemAccount
             transfer(cpi_ctx, leftover)?;
         }
+        ProtocolStats::add(&mut self.stats.matches_cancelled, 1);
+        ProtocolStats::add(&mut self.stats.leftover_swept, leftover.saturating_sub(kept_penalty));
+
+        // Spectators get their side bets back when the match never finishes
+        if self.match_account.has_side_pool {
+            self.side_pool
+                .as_mut()
+                .ok_or(ErrorCode::InvalidSidePool)?
+                .void()?;
+        }
 
         Ok(())
     }
+
+    /// Rebates the canceller's referrer its share of the protocol's penalty before the rest is swept to the treasury,
+    /// returns the rebate.
+    fn pay_referrer(&mut self, actor: Pubkey, penalty: u64) -> Result<u64> {
+        let referrer = if actor == self.match_account.player_a {
+            self.match_account.referrer_a
+        } else {
+            self.match_account.referrer_b
+        };
+        let Some(referrer) = referrer else {
+            return Ok(0);
+        };
+
+        let share = (penalty as u128 * self.config.referral_fee_bps as u128 / 10_000) as u64;
+        if share == 0 {
+            return Ok(0);
+        }
+
+        let rewards = self.referrer_rewards.as_mut().ok_or(ErrorCode::InvalidReferralError)?;
+        require_keys_eq!(rewards.referrer, referrer, ErrorCode::InvalidReferralError);
+
+        let transfer_accounts = Transfer {
+            from: self.vault.to_account_info(),
+            to: rewards.to_account_info(),
+        };
+
+        let signer_seeds: &[&[&[u8]]; 1] = &[&[
+            b"vault",
+            self.match_account.to_account_info().key.as_ref(),
+            &[self.match_account.vault_bump],
+        ]];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.system_program.to_account_info(),
+            transfer_accounts,
+            signer_seeds,
+        );
+
+        transfer(cpi_ctx, share)?;
+        rewards.credit(share)?;
+        ProtocolStats::add(&mut self.stats.referral_rebates, share);
+
+        Ok(share)
+    }
 }
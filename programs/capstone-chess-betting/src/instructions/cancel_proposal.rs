@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::Proposal;
+
+/// The proposer withdraws a proposal that hasn't been executed.
+#[derive(Accounts)]
+pub struct CancelProposal<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"admin_proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        has_one = proposer,
+        close = proposer
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+impl<'info> CancelProposal<'info> {
+    pub fn cancel_proposal(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::MatchConfig;
+use crate::PendingWithdrawal;
+use crate::Role;
+use crate::RoleAssignment;
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct CancelWithdrawal<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump,
+        constraint = config.has_role(Role::Treasurer, &authority.key(), &role_assignment) @ ErrorCode::MissingRoleError
+    )]
+    pub config: Account<'info, MatchConfig>,
+
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        mut,
+        seeds = [b"withdrawal", withdrawal.id.to_le_bytes().as_ref()],
+        bump = withdrawal.bump,
+        has_one = queued_by,
+        close = queued_by
+    )]
+    pub withdrawal: Account<'info, PendingWithdrawal>,
+
+    #[account(mut)]
+    pub queued_by: SystemAccount<'info>,
+}
+
+impl<'info> CancelWithdrawal<'info> {
+    pub fn cancel_withdrawal(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
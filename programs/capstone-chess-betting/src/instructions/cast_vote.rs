@@ -0,0 +1,262 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::transfer;
+use anchor_lang::system_program::Transfer;
+
+use crate::bond::{proposer_bond, slash_oracle_bond};
+use crate::payout::Payout;
+use crate::DisputeCase;
+use crate::GameLog;
+use crate::MatchConfig;
+use crate::MatchState;
+use crate::ProtocolStats;
+use crate::OracleBond;
+use crate::ReferrerRewards;
+use crate::ResultProposal;
+use crate::ResultReason;
+use crate::SideOutcome;
+use crate::SidePool;
+use crate::Status::*;
+
+use crate::error::ErrorCode;
+
+/// An assigned arbiter votes on an escalated dispute, the vote that reaches the threshold settles the match.
+#[derive(Accounts)]
+#[instruction(code: String)]
+pub struct CastVote<'info> {
+    #[account(mut)]
+    pub arbiter: Signer<'info>,
+
+    #[account(mut)]
+    pub player_a: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub player_b: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"match", match_account.seed.to_le_bytes().as_ref(), code.as_bytes(), match_account.player_a.key().as_ref()],
+        bump = match_account.bump,
+        has_one = payer,
+    )]
+    pub match_account: Account<'info, MatchState>,
+
+    #[account(mut)]
+    pub payer: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", match_account.key().as_ref()],
+        bump = match_account.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", match_account.key().as_ref()],
+        bump = proposal.bump,
+        has_one = proposer
+    )]
+    pub proposal: Account<'info, ResultProposal>,
+
+    #[account(mut)]
+    pub proposer: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"bond_vault", proposal.key().as_ref()],
+        bump = proposal.bond_vault_bump,
+    )]
+    pub bond_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        address = proposal.disputer.ok_or(ErrorCode::InvalidPlayerError)?
+    )]
+    pub disputer: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"oracle_bond", proposer.key().as_ref()],
+        bump = proposer_bond.bump,
+    )]
+    pub proposer_bond: Option<Account<'info, OracleBond>>,
+
+    #[account(
+        mut,
+        seeds = [b"oracle_bond_vault", proposer_bond.as_ref().ok_or(ErrorCode::OracleNotBondedError)?.key().as_ref()],
+        bump = proposer_bond.as_ref().ok_or(ErrorCode::OracleNotBondedError)?.vault_bump,
+    )]
+    pub proposer_bond_vault: Option<SystemAccount<'info>>,
+
+    #[account(
+        seeds = [b"game_log", match_account.key().as_ref()],
+        bump = game_log.bump,
+    )]
+    pub game_log: Option<Account<'info, GameLog>>,
+
+    #[account(
+        mut,
+        seeds = [b"dispute_case", proposal.key().as_ref()],
+        bump = dispute_case.bump,
+    )]
+    pub dispute_case: Account<'info, DisputeCase>,
+
+    #[account(
+        mut,
+        address = dispute_case.payer
+    )]
+    pub case_payer: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump
+    )]
+    pub config: Account<'info, MatchConfig>,
+
+    #[account(
+        mut,        //SystemAccount don't need to be initialized
+        seeds = [b"treasury"],
+        bump = config.treasury_bump
+    )]
+    pub treasury_pda: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"side_pool", match_account.key().as_ref()],
+        bump = side_pool.bump,
+    )]
+    pub side_pool: Option<Account<'info, SidePool>>,
+
+    #[account(
+        mut,
+        seeds = [b"side_vault", side_pool.as_ref().ok_or(ErrorCode::InvalidSidePool)?.key().as_ref()],
+        bump = side_pool.as_ref().ok_or(ErrorCode::InvalidSidePool)?.vault_bump,
+    )]
+    pub side_vault: Option<SystemAccount<'info>>,
+
+    // needed for each player with a referrer on the match
+    #[account(
+        mut,
+        seeds = [b"referrer_rewards", referrer_rewards_a.referrer.as_ref()],
+        bump = referrer_rewards_a.bump,
+    )]
+    pub referrer_rewards_a: Option<Account<'info, ReferrerRewards>>,
+
+    #[account(
+        mut,
+        seeds = [b"referrer_rewards", referrer_rewards_b.referrer.as_ref()],
+        bump = referrer_rewards_b.bump,
+    )]
+    pub referrer_rewards_b: Option<Account<'info, ReferrerRewards>>,
+
+    #[account(
+        mut,
+        seeds = [b"stats", [stats.shard].as_ref()],
+        bump = stats.bump,
+    )]
+    pub stats: Account<'info, ProtocolStats>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CastVote<'info> {
+    pub fn cast_vote(&mut self, _code: String, outcome: SideOutcome) -> Result<()> {
+        require!(
+            self.match_account.status == Proposed,
+            ErrorCode::InvalidMatchError
+        );
+        require!(
+            Clock::get()?.unix_timestamp < self.dispute_case.deadline,
+            ErrorCode::VoteWindowClosedError
+        );
+
+        let backing = self.dispute_case.vote(self.arbiter.key(), outcome)?;
+        self.pay_arbiter_fee()?;
+
+        if backing < self.dispute_case.threshold as usize {
+            return Ok(());
+        }
+
+        // a failed dispute forfeits what the panel's fees left of the bond to the opponent it held up,
+        // a successful one gets it back
+        let winner = self.match_account.winner_for(outcome);
+        let bond_to = if winner == self.proposal.winner {
+            self.disputer_opponent()
+        } else {
+            self.disputer.to_account_info()
+        };
+        self.release_bond(bond_to, self.bond_vault.lamports())?;
+
+        // an overturned oracle result compensates the disputer out of the oracle's bond
+        if let Some(bond) = proposer_bond(&self.config, &self.proposer.key(), &mut self.proposer_bond)? {
+            bond.open_proposals = bond.open_proposals.saturating_sub(1);
+            if winner != self.proposal.winner {
+                let bond_vault = self
+                    .proposer_bond_vault
+                    .as_ref()
+                    .ok_or(ErrorCode::OracleNotBondedError)?;
+                let amount = bond.slash_amount(&self.config);
+                slash_oracle_bond(bond, bond_vault, self.disputer.to_account_info(), amount, &self.system_program)?;
+            }
+        }
+
+        self.match_account.status = InProgress;
+        self.payout().settle(winner, ResultReason::Adjudication)?;
+
+        self.match_account.close(self.payer.to_account_info())?;
+        self.proposal.close(self.proposer.to_account_info())?;
+        self.dispute_case.close(self.case_payer.to_account_info())
+    }
+
+    /// Paid out of the dispute bond, escalate_dispute made sure it covers a vote from every arbiter.
+    fn pay_arbiter_fee(&self) -> Result<()> {
+        self.release_bond(self.arbiter.to_account_info(), self.dispute_case.arbiter_fee)
+    }
+
+    /// The player who didn't dispute, held up by a dispute that didn't succeed.
+    fn disputer_opponent(&self) -> AccountInfo<'info> {
+        if self.disputer.key() == self.player_a.key() {
+            self.player_b.to_account_info()
+        } else {
+            self.player_a.to_account_info()
+        }
+    }
+
+    fn release_bond(&self, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+        let transfer_accounts = Transfer {
+            from: self.bond_vault.to_account_info(),
+            to,
+        };
+
+        let signer_seeds: &[&[&[u8]]; 1] = &[&[
+            b"bond_vault",
+            self.proposal.to_account_info().key.as_ref(),
+            &[self.proposal.bond_vault_bump],
+        ]];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.system_program.to_account_info(),
+            transfer_accounts,
+            signer_seeds,
+        );
+
+        transfer(cpi_ctx, amount)
+    }
+
+    fn payout(&mut self) -> Payout<'_, 'info> {
+        Payout {
+            player_a: self.player_a.to_account_info(),
+            player_b: self.player_b.to_account_info(),
+            match_account: &mut self.match_account,
+            vault: &self.vault,
+            game_log: &self.game_log,
+            config: &self.config,
+            treasury_pda: &self.treasury_pda,
+            side_pool: &mut self.side_pool,
+            side_vault: &self.side_vault,
+            stats: &mut self.stats,
+            referrer_rewards: [self.referrer_rewards_a.as_mut(), self.referrer_rewards_b.as_mut()],
+            system_program: &self.system_program,
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions;
+
+use crate::ChannelClaim;
+use crate::ChannelState;
+use crate::MatchState;
+
+use crate::error::ErrorCode;
+
+/// Replaces a submitted channel state with a newer one both players signed, before the window closes.
+#[derive(Accounts)]
+#[instruction(code: String)]
+pub struct ChallengeChannelState<'info> {
+    pub challenger: Signer<'info>,
+
+    #[account(
+        seeds = [b"match", match_account.seed.to_le_bytes().as_ref(), code.as_bytes(), match_account.player_a.key().as_ref()],
+        bump = match_account.bump,
+    )]
+    pub match_account: Account<'info, MatchState>,
+
+    #[account(
+        mut,
+        seeds = [b"channel", match_account.key().as_ref()],
+        bump = channel_claim.bump,
+    )]
+    pub channel_claim: Account<'info, ChannelClaim>,
+
+    /// CHECK: the instructions sysvar, read for the Ed25519 verifications
+    #[account(address = instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+impl<'info> ChallengeChannelState<'info> {
+    pub fn challenge_channel_state(&mut self, _code: String, state: ChannelState) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp < self.channel_claim.challenge_deadline,
+            ErrorCode::ChallengeWindowClosedError
+        );
+        require!(
+            state.nonce > self.channel_claim.nonce,
+            ErrorCode::StaleChannelStateError
+        );
+        state.verify(&self.instructions, self.match_account.key(), &self.match_account)?;
+
+        self.channel_claim.store(&state);
+
+        Ok(())
+    }
+}
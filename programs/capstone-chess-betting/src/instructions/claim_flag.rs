@@ -0,0 +1,158 @@
+use anchor_lang::prelude::*;
+
+use crate::chess::{Board, Color};
+use crate::payout::Payout;
+use crate::BoardStatus;
+use crate::GameLog;
+use crate::MatchConfig;
+use crate::MatchState;
+use crate::ProtocolStats;
+use crate::ReferrerRewards;
+use crate::ResultReason;
+use crate::SidePool;
+use crate::Status::*;
+
+use crate::error::ErrorCode;
+
+/// Lets a player win once the opponent, who is on move, has run out of time.
+#[derive(Accounts)]
+#[instruction(code: String)]
+pub struct ClaimFlag<'info> {
+    pub claimant: Signer<'info>,
+
+    #[account(mut)]
+    pub player_a: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub player_b: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"match", match_account.seed.to_le_bytes().as_ref(), code.as_bytes(), match_account.player_a.key().as_ref()],
+        bump = match_account.bump,
+        has_one = payer,
+        close = payer
+    )]
+    pub match_account: Account<'info, MatchState>,
+
+    #[account(mut)]
+    pub payer: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", match_account.key().as_ref()],
+        bump = match_account.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_log", match_account.key().as_ref()],
+        bump = game_log.bump,
+    )]
+    pub game_log: Option<Account<'info, GameLog>>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump
+    )]
+    pub config: Account<'info, MatchConfig>,
+
+    #[account(
+        mut,        //SystemAccount don't need to be initialized
+        seeds = [b"treasury"],
+        bump = config.treasury_bump
+    )]
+    pub treasury_pda: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"side_pool", match_account.key().as_ref()],
+        bump = side_pool.bump,
+    )]
+    pub side_pool: Option<Account<'info, SidePool>>,
+
+    #[account(
+        mut,
+        seeds = [b"side_vault", side_pool.as_ref().ok_or(ErrorCode::InvalidSidePool)?.key().as_ref()],
+        bump = side_pool.as_ref().ok_or(ErrorCode::InvalidSidePool)?.vault_bump,
+    )]
+    pub side_vault: Option<SystemAccount<'info>>,
+
+    // needed for each player with a referrer on the match
+    #[account(
+        mut,
+        seeds = [b"referrer_rewards", referrer_rewards_a.referrer.as_ref()],
+        bump = referrer_rewards_a.bump,
+    )]
+    pub referrer_rewards_a: Option<Account<'info, ReferrerRewards>>,
+
+    #[account(
+        mut,
+        seeds = [b"referrer_rewards", referrer_rewards_b.referrer.as_ref()],
+        bump = referrer_rewards_b.bump,
+    )]
+    pub referrer_rewards_b: Option<Account<'info, ReferrerRewards>>,
+
+    #[account(
+        mut,
+        seeds = [b"stats", [stats.shard].as_ref()],
+        bump = stats.bump,
+    )]
+    pub stats: Account<'info, ProtocolStats>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimFlag<'info> {
+    pub fn claim_flag(&mut self, _code: String) -> Result<()> {
+        require!(
+            self.match_account.status == InProgress,
+            ErrorCode::InvalidMatchError
+        );
+
+        let time_control = self.match_account.time_control;
+        let white = self.match_account.white;
+        let black = self.match_account.black();
+        let game_log = self.game_log.as_mut().ok_or(ErrorCode::InvalidGameLogError)?;
+        require!(!game_log.is_finished(), ErrorCode::GameFinishedError);
+
+        let on_move = Board::unpack(&game_log.board).side_to_move;
+        let opponent = match on_move {
+            Color::White => black,
+            Color::Black => white,
+        };
+        require!(
+            opponent == Some(self.claimant.key()),
+            ErrorCode::InvalidPlayerError
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            game_log.has_flagged(on_move, &time_control, now),
+            ErrorCode::FlagNotFallenError
+        );
+
+        game_log.status = BoardStatus::Timeout;
+        game_log.winner = opponent;
+
+        self.payout().settle(opponent, ResultReason::Timeout)
+    }
+
+    fn payout(&mut self) -> Payout<'_, 'info> {
+        Payout {
+            player_a: self.player_a.to_account_info(),
+            player_b: self.player_b.to_account_info(),
+            match_account: &mut self.match_account,
+            vault: &self.vault,
+            game_log: &self.game_log,
+            config: &self.config,
+            treasury_pda: &self.treasury_pda,
+            side_pool: &mut self.side_pool,
+            side_vault: &self.side_vault,
+            stats: &mut self.stats,
+            referrer_rewards: [self.referrer_rewards_a.as_mut(), self.referrer_rewards_b.as_mut()],
+            system_program: &self.system_program,
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::ReferrerRewards;
+
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct ClaimReferralRewards<'info> {
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"referrer_rewards", referrer.key().as_ref()],
+        bump = referrer_rewards.bump,
+        has_one = referrer
+    )]
+    pub referrer_rewards: Account<'info, ReferrerRewards>,
+}
+
+impl<'info> ClaimReferralRewards<'info> {
+    pub fn claim_referral_rewards(&mut self) -> Result<()> {
+        let amount = self.referrer_rewards.claimable;
+        require!(amount > 0, ErrorCode::InsufficientBalance);
+
+        // the program owns the rewards account, so its lamports can be moved without a CPI
+        self.referrer_rewards.sub_lamports(amount)?;
+        self.referrer.add_lamports(amount)?;
+        self.referrer_rewards.claimable = 0;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::transfer;
+use anchor_lang::system_program::Transfer;
+
+use crate::SideBet;
+use crate::SidePool;
+
+#[derive(Accounts)]
+pub struct ClaimSideBet<'info> {
+    #[account(mut)]
+    pub bettor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"side_pool", side_pool.match_account.as_ref()],
+        bump = side_pool.bump,
+    )]
+    pub side_pool: Account<'info, SidePool>,
+
+    #[account(
+        mut,
+        seeds = [b"side_bet", side_pool.key().as_ref(), bettor.key().as_ref()],
+        bump = side_bet.bump,
+        has_one = bettor,
+        close = bettor
+    )]
+    pub side_bet: Account<'info, SideBet>,
+
+    #[account(
+        mut,
+        seeds = [b"side_vault", side_pool.key().as_ref()],
+        bump = side_pool.vault_bump,
+    )]
+    pub side_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimSideBet<'info> {
+    pub fn claim_side_bet(&mut self) -> Result<()> {
+        // losing bets still close here so the bettor gets their rent back
+        let amount = self.side_pool.payout_for(&self.side_bet)?;
+        self.side_pool.open_bets -= 1;
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let transfer_accounts = Transfer {
+            from: self.side_vault.to_account_info(),
+            to: self.bettor.to_account_info(),
+        };
+
+        let signer_seeds: &[&[&[u8]]; 1] = &[&[
+            b"side_vault",
+            self.side_pool.to_account_info().key.as_ref(),
+            &[self.side_pool.vault_bump],
+        ]];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.system_program.to_account_info(),
+            transfer_accounts,
+            signer_seeds,
+        );
+
+        transfer(cpi_ctx, amount)?;
+
+        Ok(())
+    }
+}
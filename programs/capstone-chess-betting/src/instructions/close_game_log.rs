@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::GameLog;
+
+use crate::error::ErrorCode;
+
+/// Returns the log's rent once its match has been settled or cancelled.
+#[derive(Accounts)]
+pub struct CloseGameLog<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: only used to confirm the match account has been closed
+    #[account(address = game_log.match_account)]
+    pub match_account: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_log", game_log.match_account.as_ref()],
+        bump = game_log.bump,
+        has_one = payer,
+        close = payer
+    )]
+    pub game_log: Account<'info, GameLog>,
+}
+
+impl<'info> CloseGameLog<'info> {
+    pub fn close_game_log(&mut self) -> Result<()> {
+        require!(
+            self.match_account.data_is_empty(),
+            ErrorCode::InvalidMatchError
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::transfer;
+use anchor_lang::system_program::Transfer;
+
+use crate::SidePool;
+use crate::SidePoolStatus;
+
+use crate::error::ErrorCode;
+
+/// Returns the pool's and its vault's rent once the pool is settled and every bet has been claimed.
+/// Rounding dust left over from the payout shares goes along with the vault's rent.
+#[derive(Accounts)]
+pub struct CloseSidePool<'info> {
+    pub cranker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"side_pool", side_pool.match_account.as_ref()],
+        bump = side_pool.bump,
+        has_one = payer,
+        close = payer
+    )]
+    pub side_pool: Account<'info, SidePool>,
+
+    #[account(
+        mut,
+        seeds = [b"side_vault", side_pool.key().as_ref()],
+        bump = side_pool.vault_bump,
+    )]
+    pub side_vault: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub payer: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CloseSidePool<'info> {
+    pub fn close_side_pool(&mut self) -> Result<()> {
+        require!(
+            self.side_pool.status != SidePoolStatus::Open,
+            ErrorCode::SidePoolNotSettled
+        );
+        require!(self.side_pool.open_bets == 0, ErrorCode::InvalidSidePool);
+
+        let transfer_accounts = Transfer {
+            from: self.side_vault.to_account_info(),
+            to: self.payer.to_account_info(),
+        };
+
+        let signer_seeds: &[&[&[u8]]; 1] = &[&[
+            b"side_vault",
+            self.side_pool.to_account_info().key.as_ref(),
+            &[self.side_pool.vault_bump],
+        ]];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.system_program.to_account_info(),
+            transfer_accounts,
+            signer_seeds,
+        );
+
+        transfer(cpi_ctx, self.side_vault.lamports())
+    }
+}
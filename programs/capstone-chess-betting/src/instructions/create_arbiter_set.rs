@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::ArbiterSet;
+use crate::MatchConfig;
+use crate::Role;
+use crate::RoleAssignment;
+
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct CreateArbiterSet<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.config_bump,
+        constraint = config.has_role(Role::Admin, &authority.key(), &role_assignment) @ ErrorCode::InvalidAdminError
+    )]
+    pub config: Account<'info, MatchConfig>,
+
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ArbiterSet::INIT_SPACE,
+        seeds = [b"arbiter_set"],
+        bump,
+    )]
+    pub arbiter_set: Account<'info, ArbiterSet>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateArbiterSet<'info> {
+    pub fn create_arbiter_set(
+        &mut self,
+        arbiters: Vec<Pubkey>,
+        threshold: u8,
+        arbiter_fee: u64,
+        vote_window: i64,
+        bumps: &CreateArbiterSetBumps,
+    ) -> Result<()> {
+        self.arbiter_set.bump = bumps.arbiter_set;
+        self.arbiter_set.configure(arbiters, threshold, arbiter_fee, vote_window)?;
+        self.config.arbiter_set = Some(self.arbiter_set.key());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::FeeShare;
+use crate::FeeSplit;
+use crate::MatchConfig;
+use crate::Role;
+use crate::RoleAssignment;
+
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct CreateFeeSplit<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump,
+        constraint = config.has_role(Role::Admin, &authority.key(), &role_assignment) @ ErrorCode::InvalidAdminError
+    )]
+    pub config: Account<'info, MatchConfig>,
+
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + FeeSplit::INIT_SPACE,
+        seeds = [b"fee_split"],
+        bump,
+    )]
+    pub fee_split: Account<'info, FeeSplit>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateFeeSplit<'info> {
+    pub fn create_fee_split(
+        &mut self,
+        shares: Vec<FeeShare>,
+        reserve: u64,
+        bumps: &CreateFeeSplitBumps,
+    ) -> Result<()> {
+        self.fee_split.bump = bumps.fee_split;
+        self.fee_split.configure(shares, reserve)
+    }
+}
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+use crate::MatchConfig;
+use crate::Proposal;
+use crate::ProposalAccount;
+use crate::{MAX_PROPOSAL_ACCOUNTS, MAX_PROPOSAL_DATA};
+use crate::error::ErrorCode;
+
+/// An admin signer proposes an instruction of this program to be run as the admin PDA, counting as its first approval.
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.config_bump,
+        constraint = config.admin_signers.contains(&proposer.key()) @ ErrorCode::NotAnAdminSignerError
+    )]
+    pub config: Account<'info, MatchConfig>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + Proposal::INIT_SPACE,
+        seeds = [b"admin_proposal", config.proposal_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateProposal<'info> {
+    pub fn create_proposal(
+        &mut self,
+        accounts: Vec<ProposalAccount>,
+        data: Vec<u8>,
+        bumps: &CreateProposalBumps,
+    ) -> Result<()> {
+        require!(
+            accounts.len() <= MAX_PROPOSAL_ACCOUNTS && data.len() <= MAX_PROPOSAL_DATA,
+            ErrorCode::InvalidProposalError
+        );
+        // the admin PDA is the only signer execute_proposal can provide
+        let admin = self.config.authority;
+        require!(
+            accounts.iter().all(|account| !account.is_signer || account.pubkey == admin),
+            ErrorCode::InvalidProposalError
+        );
+
+        self.proposal.set_inner(Proposal {
+            id: self.config.proposal_count,
+            proposer: self.proposer.key(),
+            accounts,
+            data,
+            approvals: vec![self.proposer.key()],
+            bump: bumps.proposal,
+        });
+        self.config.proposal_count += 1;
+
+        Ok(())
+    }
+}
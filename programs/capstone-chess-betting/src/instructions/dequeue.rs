@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::transfer;
+use anchor_lang::system_program::Transfer;
+
+use crate::QueueTicket;
+
+#[derive(Accounts)]
+pub struct Dequeue<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ticket", player.key().as_ref(), ticket.bet_amount.to_le_bytes().as_ref(), ticket.time_control.to_seed_bytes().as_ref()],
+        bump = ticket.bump,
+        has_one = player,
+        close = player
+    )]
+    pub ticket: Account<'info, QueueTicket>,
+
+    #[account(
+        mut,
+        seeds = [b"ticket_vault", ticket.key().as_ref()],
+        bump = ticket.vault_bump,
+    )]
+    pub ticket_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Dequeue<'info> {
+    pub fn dequeue(&mut self) -> Result<()> {
+        let transfer_accounts = Transfer {
+            from: self.ticket_vault.to_account_info(),
+            to: self.player.to_account_info(),
+        };
+
+        let signer_seeds: &[&[&[u8]]; 1] = &[&[
+            b"ticket_vault",
+            self.ticket.to_account_info().key.as_ref(),
+            &[self.ticket.vault_bump],
+        ]];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.system_program.to_account_info(),
+            transfer_accounts,
+            signer_seeds,
+        );
+
+        transfer(cpi_ctx, self.ticket_vault.lamports())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::transfer;
+use anchor_lang::system_program::Transfer;
+
+use crate::MatchConfig;
+use crate::MatchState;
+use crate::ResultProposal;
+
+use crate::error::ErrorCode;
+
+/// Either player locks the dispute bond to stop a proposed result from being finalized.
+#[derive(Accounts)]
+#[instruction(code: String)]
+pub struct DisputeResult<'info> {
+    #[account(mut)]
+    pub disputer: Signer<'info>,
+
+    #[account(
+        seeds = [b"match", match_account.seed.to_le_bytes().as_ref(), code.as_bytes(), match_account.player_a.key().as_ref()],
+        bump = match_account.bump,
+    )]
+    pub match_account: Account<'info, MatchState>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", match_account.key().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, ResultProposal>,
+
+    #[account(
+        mut,
+        seeds = [b"bond_vault", proposal.key().as_ref()],
+        bump = proposal.bond_vault_bump,
+    )]
+    pub bond_vault: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump
+    )]
+    pub config: Account<'info, MatchConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> DisputeResult<'info> {
+    pub fn dispute_result(&mut self, _code: String) -> Result<()> {
+        let disputer = self.disputer.key();
+        require!(
+            disputer == self.match_account.player_a || Some(disputer) == self.match_account.player_b,
+            ErrorCode::InvalidPlayerError
+        );
+        require!(
+            Clock::get()?.unix_timestamp < self.proposal.challenge_deadline,
+            ErrorCode::ChallengeWindowClosedError
+        );
+        require!(
+            self.proposal.disputer.is_none(),
+            ErrorCode::AlreadyDisputedError
+        );
+
+        let bond = self.config.dispute_bond;
+        let transfer_accounts = Transfer {
+            from: self.disputer.to_account_info(),
+            to: self.bond_vault.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), transfer_accounts);
+        transfer(cpi_ctx, bond)?;
+
+        self.proposal.disputer = Some(disputer);
+        self.proposal.dispute_bond = bond;
+
+        Ok(())
+    }
+}
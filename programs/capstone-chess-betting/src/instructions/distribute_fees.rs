@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::events::FeesDistributed;
+use crate::FeeSplit;
+use crate::MatchConfig;
+use crate::ProtocolStats;
+
+use crate::error::ErrorCode;
+
+/// Permissionless crank paying every beneficiary its share of the treasury above rent and the reserve.
+/// The beneficiaries are passed as remaining accounts, in the split's order. Like any withdrawal they
+/// have to be active withdraw destinations, and the payout counts against the epoch cap.
+#[derive(Accounts)]
+pub struct DistributeFees<'info> {
+    pub cranker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.config_bump,
+    )]
+    pub config: Account<'info, MatchConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_split"],
+        bump = fee_split.bump,
+    )]
+    pub fee_split: Account<'info, FeeSplit>,
+
+    #[account(
+        mut,        //SystemAccount don't need to be initialized
+        seeds = [b"treasury"],
+        bump = config.treasury_bump,
+    )]
+    pub treasury_pda: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"stats", [stats.shard].as_ref()],
+        bump = stats.bump,
+    )]
+    pub stats: Account<'info, ProtocolStats>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> DistributeFees<'info> {
+    pub fn distribute_fees(&mut self, beneficiaries: &[AccountInfo<'info>]) -> Result<()> {
+        require!(
+            beneficiaries.len() == self.fee_split.beneficiaries.len()
+                && beneficiaries
+                    .iter()
+                    .zip(self.fee_split.beneficiaries.iter())
+                    .all(|(info, beneficiary)| info.key() == beneficiary.key),
+            ErrorCode::InvalidFeeSplitError
+        );
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            self.fee_split
+                .beneficiaries
+                .iter()
+                .all(|beneficiary| self.config.is_withdraw_destination(&beneficiary.key, now)),
+            ErrorCode::InvalidDestinationError
+        );
+
+        let rent_min = Rent::get()?.minimum_balance(self.treasury_pda.data_len());
+        let available = self
+            .treasury_pda
+            .lamports()
+            .saturating_sub(rent_min)
+            .saturating_sub(self.fee_split.reserve)
+            .min(self.config.epoch_allowance(now));
+        require!(available > 0, ErrorCode::InsufficientBalance);
+
+        let signer_seeds: &[&[&[u8]]; 1] = &[&[
+            b"treasury",
+            &[self.config.treasury_bump]
+        ]];
+
+        let mut distributed = 0u64;
+        for (info, beneficiary) in beneficiaries.iter().zip(self.fee_split.beneficiaries.iter_mut()) {
+            let share = beneficiary.share_of(available);
+            if share == 0 {
+                continue;
+            }
+
+            let transfer_accounts = Transfer {
+                from: self.treasury_pda.to_account_info(),
+                to: info.clone(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(self.system_program.to_account_info(), transfer_accounts, signer_seeds);
+            transfer(cpi_ctx, share)?;
+
+            beneficiary.total_received = beneficiary
+                .total_received
+                .checked_add(share)
+                .ok_or(ErrorCode::MathOverflow)?;
+            distributed += share;
+        }
+
+        self.config.record_epoch_withdrawal(distributed, now)?;
+        self.config.total_withdrawn = self
+            .config
+            .total_withdrawn
+            .checked_add(distributed)
+            .ok_or(ErrorCode::MathOverflow)?;
+        ProtocolStats::add(&mut self.stats.total_withdrawn, distributed);
+        ProtocolStats::add(&mut self.stats.fees_distributed, distributed);
+
+        self.fee_split.total_distributed = self
+            .fee_split
+            .total_distributed
+            .checked_add(distributed)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        emit!(FeesDistributed {
+            amount: distributed,
+            total_distributed: self.fee_split.total_distributed,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::transfer;
+use anchor_lang::system_program::Transfer;
+
+use crate::MatchConfig;
+use crate::QueueTicket;
+use crate::TimeControl;
+
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+#[instruction(bet_amount: u64, time_control: TimeControl)]
+pub struct Enqueue<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        init,
+        payer = player,
+        space = 8 + QueueTicket::INIT_SPACE,
+        seeds = [b"ticket", player.key().as_ref(), bet_amount.to_le_bytes().as_ref(), time_control.to_seed_bytes().as_ref()],
+        bump,
+    )]
+    pub ticket: Account<'info, QueueTicket>,
+
+    #[account(
+        mut,        //SystemAccount don't need to be initialized
+        seeds = [b"ticket_vault", ticket.key().as_ref()],
+        bump
+    )]
+    pub ticket_vault: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump,
+        constraint = !config.paused @ ErrorCode::PausedError
+    )]
+    pub config: Account<'info, MatchConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Enqueue<'info> {
+    pub fn enqueue(
+        &mut self,
+        bet_amount: u64,
+        time_control: TimeControl,
+        color_commitment: [u8; 32],
+        bumps: &EnqueueBumps,
+    ) -> Result<()> {
+        time_control.validate(&self.config)?;
+        self.config.validate_bet(bet_amount)?;
+        require!(
+            self.player.get_lamports() > bet_amount,
+            ErrorCode::InsufficientBalance
+        );
+
+        self.ticket.set_inner(QueueTicket {
+            player: self.player.key(),
+            bet_amount,
+            time_control,
+            color_commitment,
+            created_at: Clock::get()?.unix_timestamp,
+            bump: bumps.ticket,
+            vault_bump: bumps.ticket_vault,
+        });
+
+        let transfer_accounts = Transfer {
+            from: self.player.to_account_info(),
+            to: self.ticket_vault.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), transfer_accounts);
+
+        transfer(cpi_ctx, bet_amount)?;
+        Ok(())
+    }
+}
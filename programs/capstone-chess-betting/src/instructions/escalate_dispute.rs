@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+
+use crate::ArbiterSet;
+use crate::DisputeCase;
+use crate::MatchConfig;
+use crate::MatchState;
+use crate::ResultProposal;
+use crate::Status::*;
+
+use crate::error::ErrorCode;
+
+/// Hands a disputed proposal to the arbiter panel instead of the admin.
+#[derive(Accounts)]
+#[instruction(code: String)]
+pub struct EscalateDispute<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [b"match", match_account.seed.to_le_bytes().as_ref(), code.as_bytes(), match_account.player_a.key().as_ref()],
+        bump = match_account.bump,
+    )]
+    pub match_account: Account<'info, MatchState>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", match_account.key().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, ResultProposal>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + DisputeCase::INIT_SPACE,
+        seeds = [b"dispute_case", proposal.key().as_ref()],
+        bump,
+    )]
+    pub dispute_case: Account<'info, DisputeCase>,
+
+    #[account(
+        seeds = [b"arbiter_set"],
+        bump = arbiter_set.bump,
+        constraint = config.arbiter_set == Some(arbiter_set.key()) @ ErrorCode::InvalidArbiterSetError
+    )]
+    pub arbiter_set: Account<'info, ArbiterSet>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump
+    )]
+    pub config: Account<'info, MatchConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> EscalateDispute<'info> {
+    pub fn escalate_dispute(&mut self, _code: String, bumps: &EscalateDisputeBumps) -> Result<()> {
+        let payer = self.payer.key();
+        require!(
+            payer == self.match_account.player_a
+                || Some(payer) == self.match_account.player_b
+                || payer == self.config.authority,
+            ErrorCode::InvalidPlayerError
+        );
+        require!(
+            self.match_account.status == Proposed,
+            ErrorCode::InvalidMatchError
+        );
+        require!(
+            self.proposal.disputer.is_some(),
+            ErrorCode::NotDisputedError
+        );
+        require!(!self.proposal.escalated, ErrorCode::DisputeEscalatedError);
+        require!(!self.proposal.escalation_used, ErrorCode::EscalationUsedError);
+        // arbiters are paid from the bond alone, it has to cover a vote from every one of them
+        let panel_fees = self
+            .arbiter_set
+            .arbiter_fee
+            .checked_mul(self.arbiter_set.arbiters.len() as u64)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(
+            self.proposal.dispute_bond.saturating_sub(Rent::get()?.minimum_balance(0)) >= panel_fees,
+            ErrorCode::ArbiterFeeError
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        self.dispute_case.set_inner(DisputeCase {
+            proposal: self.proposal.key(),
+            payer,
+            arbiters: self.arbiter_set.arbiters.clone(),
+            threshold: self.arbiter_set.threshold,
+            arbiter_fee: self.arbiter_set.arbiter_fee,
+            votes: Vec::new(),
+            deadline: now
+                .checked_add(self.arbiter_set.vote_window)
+                .ok_or(ErrorCode::MathOverflow)?,
+            bump: bumps.dispute_case,
+        });
+        self.proposal.escalated = true;
+        self.proposal.escalation_used = true;
+
+        Ok(())
+    }
+}
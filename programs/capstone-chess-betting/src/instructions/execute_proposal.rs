@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+
+use crate::MatchConfig;
+use crate::Proposal;
+use crate::error::ErrorCode;
+
+/// Runs an approved proposal's instruction with the admin PDA as signer.
+/// The instruction's accounts are passed as remaining accounts, in the proposal's order.
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    pub executor: Signer<'info>,
+
+    // not mut, the proposed instruction may rewrite the config and it must not be overwritten on exit
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump,
+    )]
+    pub config: Account<'info, MatchConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"admin_proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        has_one = proposer,
+        close = proposer
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(mut)]
+    pub proposer: SystemAccount<'info>,
+}
+
+impl<'info> ExecuteProposal<'info> {
+    pub fn execute_proposal(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        require!(
+            self.config.admin_threshold > 0
+                && self.proposal.valid_approvals(&self.config.admin_signers)
+                    >= self.config.admin_threshold as usize,
+            ErrorCode::ThresholdNotMetError
+        );
+        require!(
+            remaining_accounts.len() == self.proposal.accounts.len()
+                && remaining_accounts
+                    .iter()
+                    .zip(self.proposal.accounts.iter())
+                    .all(|(info, account)| info.key() == account.pubkey),
+            ErrorCode::InvalidProposalError
+        );
+
+        let instruction = Instruction {
+            program_id: crate::ID,
+            accounts: self
+                .proposal
+                .accounts
+                .iter()
+                .map(|account| AccountMeta {
+                    pubkey: account.pubkey,
+                    is_signer: account.is_signer,
+                    is_writable: account.is_writable,
+                })
+                .collect(),
+            data: self.proposal.data.clone(),
+        };
+
+        let signer_seeds: &[&[&[u8]]; 1] = &[&[
+            b"admin",
+            &[self.config.admin_bump]
+        ]];
+
+        invoke_signed(&instruction, remaining_accounts, signer_seeds)?;
+
+        Ok(())
+    }
+}
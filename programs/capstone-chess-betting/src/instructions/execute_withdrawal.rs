@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::events::TreasuryWithdrawn;
+use crate::MatchConfig;
+use crate::PendingWithdrawal;
+use crate::ProtocolStats;
+use crate::error::ErrorCode;
+
+/// Pays out a queued withdrawal once its eta has passed, within the current epoch's cap.
+#[derive(Accounts)]
+pub struct ExecuteWithdrawal<'info> {
+    pub executor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.config_bump,
+    )]
+    pub config: Account<'info, MatchConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"withdrawal", withdrawal.id.to_le_bytes().as_ref()],
+        bump = withdrawal.bump,
+        has_one = destination,
+        has_one = queued_by,
+        close = queued_by
+    )]
+    pub withdrawal: Account<'info, PendingWithdrawal>,
+
+    #[account(mut)]
+    pub queued_by: SystemAccount<'info>,
+
+    #[account(
+        mut,        //SystemAccount don't need to be initialized
+        seeds = [b"treasury"],
+        bump = config.treasury_bump,
+    )]
+    pub treasury_pda: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub destination: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"stats", [stats.shard].as_ref()],
+        bump = stats.bump,
+    )]
+    pub stats: Account<'info, ProtocolStats>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl <'info> ExecuteWithdrawal<'info> {
+    pub fn execute_withdrawal(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(now >= self.withdrawal.eta, ErrorCode::WithdrawalLockedError);
+        require!(
+            self.config.is_withdraw_destination(&self.destination.key(), now),
+            ErrorCode::InvalidDestinationError
+        );
+
+        let amount = self.withdrawal.amount;
+        self.config.record_epoch_withdrawal(amount, now)?;
+
+        let transfer_accounts = Transfer {
+            from: self.treasury_pda.to_account_info(),
+            to: self.destination.to_account_info()
+        };
+
+        let signer_seeds: &[&[&[u8]]; 1] = &[&[
+            b"treasury",
+            &[self.config.treasury_bump]
+        ]];
+
+        let cpi_ctx = CpiContext::new_with_signer(self.system_program.to_account_info(), transfer_accounts, signer_seeds);
+
+        let rent_min = Rent::get()?.minimum_balance(self.treasury_pda.data_len());
+
+        let available = self.treasury_pda.lamports().saturating_sub(rent_min);
+        require!(amount <= available, ErrorCode::InsufficientBalance);
+
+        transfer(cpi_ctx, amount)?;
+
+        self.config.total_withdrawn = self
+            .config
+            .total_withdrawn
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        ProtocolStats::add(&mut self.stats.total_withdrawn, amount);
+
+        emit!(TreasuryWithdrawn {
+            destination: self.destination.key(),
+            amount,
+            total_withdrawn: self.config.total_withdrawn,
+        });
+
+        Ok(())
+    }
+}
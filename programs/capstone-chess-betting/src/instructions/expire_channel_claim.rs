@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::ChannelClaim;
+use crate::MatchState;
+use crate::Status::*;
+
+use crate::error::ErrorCode;
+
+/// Closes a claim whose window passed on a state without a result, handing the match back to play.
+/// A newer state can be submitted afterwards, the expired one and anything older can't.
+#[derive(Accounts)]
+#[instruction(code: String)]
+pub struct ExpireChannelClaim<'info> {
+    pub cranker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"match", match_account.seed.to_le_bytes().as_ref(), code.as_bytes(), match_account.player_a.key().as_ref()],
+        bump = match_account.bump,
+    )]
+    pub match_account: Account<'info, MatchState>,
+
+    #[account(
+        mut,
+        seeds = [b"channel", match_account.key().as_ref()],
+        bump = channel_claim.bump,
+        has_one = submitter,
+        close = submitter
+    )]
+    pub channel_claim: Account<'info, ChannelClaim>,
+
+    #[account(mut)]
+    pub submitter: SystemAccount<'info>,
+}
+
+impl<'info> ExpireChannelClaim<'info> {
+    pub fn expire_channel_claim(&mut self, _code: String) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp >= self.channel_claim.challenge_deadline,
+            ErrorCode::ChallengeWindowOpenError
+        );
+        require!(
+            self.channel_claim.result.is_none(),
+            ErrorCode::ChannelGameFinishedError
+        );
+
+        self.match_account.channel_nonce = self.channel_claim.nonce;
+        self.match_account.status = InProgress;
+
+        Ok(())
+    }
+}
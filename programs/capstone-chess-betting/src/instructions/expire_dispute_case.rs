@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::DisputeCase;
+use crate::ResultProposal;
+
+use crate::error::ErrorCode;
+
+/// Closes a case the panel didn't decide in time, handing the dispute back to `resolve_dispute` for good.
+#[derive(Accounts)]
+pub struct ExpireDisputeCase<'info> {
+    pub cranker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.match_account.as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, ResultProposal>,
+
+    #[account(
+        mut,
+        seeds = [b"dispute_case", proposal.key().as_ref()],
+        bump = dispute_case.bump,
+        has_one = payer,
+        close = payer
+    )]
+    pub dispute_case: Account<'info, DisputeCase>,
+
+    #[account(mut)]
+    pub payer: SystemAccount<'info>,
+}
+
+impl<'info> ExpireDisputeCase<'info> {
+    pub fn expire_dispute_case(&mut self) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp >= self.dispute_case.deadline,
+            ErrorCode::VoteWindowOpenError
+        );
+
+        self.proposal.escalated = false;
+
+        Ok(())
+    }
+}
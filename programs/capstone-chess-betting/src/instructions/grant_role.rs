@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::MatchConfig;
+use crate::Role;
+use crate::RoleAssignment;
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+#[instruction(role: Role, holder: Pubkey)]
+pub struct GrantRole<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump,
+        constraint = config.has_role(Role::Admin, &authority.key(), &role_assignment) @ ErrorCode::InvalidAdminError
+    )]
+    pub config: Account<'info, MatchConfig>,
+
+    // the granter's own admin role, not needed for the config authority
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + RoleAssignment::INIT_SPACE,
+        seeds = [b"role", [role as u8].as_ref(), holder.as_ref()],
+        bump,
+    )]
+    pub granted: Account<'info, RoleAssignment>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> GrantRole<'info> {
+    pub fn grant_role(&mut self, role: Role, holder: Pubkey, bumps: &GrantRoleBumps) -> Result<()> {
+        // a delegated admin can't hand out admin or treasury powers of its own
+        if role == Role::Admin || role == Role::Treasurer {
+            require_keys_eq!(
+                self.authority.key(),
+                self.config.authority,
+                ErrorCode::InvalidAdminError
+            );
+        }
+
+        self.granted.set_inner(RoleAssignment {
+            role,
+            holder,
+            granted_by: self.authority.key(),
+            bump: bumps.granted,
+        });
+
+        Ok(())
+    }
+}
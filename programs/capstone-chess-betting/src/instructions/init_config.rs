@@ -39,11 +39,11 @@ impl<'info> InitConfig<'info> {
             ErrorCode::InvalidAdminError
         );
 
-        self.config.set_inner(MatchConfig {
-            authority: self.authority.key(),
-            treasury_bump: bumps.treasury_pda,
-            config_bump: bumps.config,
-        });
+        self.config.set_inner(MatchConfig::new(
+            self.authority.key(),
+            bumps.treasury_pda,
+            bumps.config,
+        ));
 
         Ok(())
     }
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::ProtocolStats;
+use crate::STATS_SHARDS;
+
+use crate::error::ErrorCode;
+
+/// Creates one of the `STATS_SHARDS` stats accounts. Permissionless, a shard only ever holds counters.
+#[derive(Accounts)]
+#[instruction(shard: u8)]
+pub struct InitStats<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ProtocolStats::INIT_SPACE,
+        seeds = [b"stats", [shard].as_ref()],
+        bump
+    )]
+    pub stats: Account<'info, ProtocolStats>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitStats<'info> {
+    pub fn init_stats(&mut self, shard: u8, bumps: &InitStatsBumps) -> Result<()> {
+        require!(shard < STATS_SHARDS, ErrorCode::InvalidConfigError);
+
+        self.stats.set_inner(ProtocolStats {
+            shard,
+            matches_created: 0,
+            matches_accepted: 0,
+            matches_cancelled: 0,
+            matches_completed: 0,
+            matches_drawn: 0,
+            total_wagered: 0,
+            win_fees: 0,
+            draw_fees: 0,
+            cancel_penalties: 0,
+            cancel_compensation: 0,
+            leftover_swept: 0,
+            referral_rebates: 0,
+            side_pool_fees: 0,
+            total_withdrawn: 0,
+            fees_distributed: 0,
+            bump: bumps.stats,
+        });
+
+        Ok(())
+    }
+}
@@ -2,8 +2,14 @@ use anchor_lang::prelude::*;
 use anchor_lang::system_program::transfer;
 use anchor_lang::system_program::Transfer;
 
+use crate::MatchConfig;
 use crate::MatchState;
+use crate::ProtocolStats;
+use crate::Referral;
+use crate::ReferrerRewards;
 use crate::Status::*;
+use crate::TimeControl;
+use crate::Variant;
 
 use crate::error::ErrorCode;
 
@@ -13,9 +19,12 @@ pub struct InitializeMatch<'info> {
     #[account(mut)]
     pub player_a: Signer<'info>,
 
+    #[account(mut)]
+    pub payer: Signer<'info>, // player A or a relayer, funds the match record and the referral
+
     #[account(
         init,
-        payer = player_a,
+        payer = payer,
         space = 8 + MatchState::INIT_SPACE,
         seeds = [b"match", seed.to_le_bytes().as_ref(), code.as_bytes(), player_a.key().as_ref()],
         bump,
@@ -29,29 +38,90 @@ pub struct InitializeMatch<'info> {
     )]
     pub vault: SystemAccount<'info>,
 
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump,
+        constraint = !config.paused @ ErrorCode::PausedError
+    )]
+    pub config: Account<'info, MatchConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + Referral::INIT_SPACE,
+        seeds = [b"referral", player_a.key().as_ref()],
+        bump,
+    )]
+    pub referral: Option<Account<'info, Referral>>, // only needed when the player has or names a referrer
+
+    #[account(
+        seeds = [b"referrer_rewards", referrer_rewards.referrer.as_ref()],
+        bump = referrer_rewards.bump,
+    )]
+    pub referrer_rewards: Option<Account<'info, ReferrerRewards>>,
+
+    #[account(
+        mut,
+        seeds = [b"stats", [stats.shard].as_ref()],
+        bump = stats.bump,
+    )]
+    pub stats: Account<'info, ProtocolStats>,
+
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> InitializeMatch<'info> {
+    #[allow(clippy::too_many_arguments)]
     pub fn init_match(
         &mut self,
         seed: u64,
         _code: String,
-        match_duration: u32,
+        time_control: TimeControl,
+        variant: Variant,
+        color_commitment: [u8; 32],
         bet_amount: u64,
         player_b: Option<Pubkey>,
         winner: Option<Pubkey>,
+        referrer: Option<Pubkey>,
         bumps: &InitializeMatchBumps,
     ) -> Result<()> {
+        time_control.validate(&self.config)?;
+        self.config.validate_bet(bet_amount)?;
+        let referrer_a = Referral::record(
+            &mut self.referral,
+            &self.referrer_rewards,
+            self.player_a.key(),
+            referrer,
+            bumps.referral,
+        )?;
+
         self.match_account.set_inner(MatchState {
             seed,
             bet_amount,
-            match_duration,
+            time_control,
+            variant,
+            start_position: None,
+            color_commitment,
+            player_b_commitment: None,
+            player_b_random: [0; 32],
+            white: None,
             player_a: self.player_a.key(),
             player_b,
-            created_at: Clock::get()?.unix_timestamp, // handle this in ER if u add that or handle it off-chain that match ends after the duration
+            referrer_a,
+            referrer_b: None,
+            payer: self.payer.key(),
+            created_at: Clock::get()?.unix_timestamp, // clocks run from start_game, see GameLog and claim_flag
+            accepted_at: 0,
+            reveal_window_start: 0,
             winner,
+            result_reason: None,
             status: Waiting,
+            has_side_pool: false,
+            has_game_log: false,
+            reported_a: None,
+            reported_b: None,
+            settlement_nonce: Clock::get()?.slot,
+            channel_nonce: 0,
             bump: bumps.match_account,
             vault_bump: bumps.vault,
         });
@@ -77,6 +147,9 @@ impl<'info> InitializeMatch<'info> {
         let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), transfer_accounts);
 
         transfer(cpi_ctx, self.match_account.bet_amount)?;
+
+        ProtocolStats::add(&mut self.stats.matches_created, 1);
+        ProtocolStats::add(&mut self.stats.total_wagered, self.match_account.bet_amount);
         Ok(())
     }
 }
@@ -3,11 +3,111 @@ pub mod accept_match;
 pub mod init_config;
 pub mod cancel_match;
 pub mod final_payments;
-pub mod withdraw_from_treasury;
+pub mod queue_withdrawal;
+pub mod execute_withdrawal;
+pub mod cancel_withdrawal;
+pub mod update_config;
+pub mod open_side_pool;
+pub mod place_side_bet;
+pub mod claim_side_bet;
+pub mod enqueue;
+pub mod dequeue;
+pub mod pair;
+pub mod reveal_colors;
+pub mod claim_reveal_timeout;
+pub mod start_game;
+pub mod submit_move;
+pub mod settle_game;
+pub mod close_game_log;
+pub mod claim_flag;
+pub mod submit_channel_state;
+pub mod challenge_channel_state;
+pub mod settle_channel;
+pub mod settle_with_attestation;
+pub mod report_result;
+pub mod propose_result;
+pub mod dispute_result;
+pub mod finalize_result;
+pub mod resolve_dispute;
+pub mod create_arbiter_set;
+pub mod update_arbiter_set;
+pub mod escalate_dispute;
+pub mod cast_vote;
+pub mod expire_dispute_case;
+pub mod bond_oracle;
+pub mod request_unbond;
+pub mod withdraw_oracle_bond;
+pub mod slash_oracle;
+pub mod create_proposal;
+pub mod approve_proposal;
+pub mod execute_proposal;
+pub mod cancel_proposal;
+pub mod grant_role;
+pub mod revoke_role;
+pub mod set_paused;
+pub mod update_fees;
+pub mod create_fee_split;
+pub mod update_fee_split;
+pub mod distribute_fees;
+pub mod register_referrer;
+pub mod claim_referral_rewards;
+pub mod close_side_pool;
+pub mod expire_channel_claim;
+pub mod init_stats;
 
 pub use initialize_match::*;
 pub use accept_match::*;
 pub use init_config::*;
 pub use cancel_match::*;
 pub use final_payments::*;
-pub use withdraw_from_treasury::*;
\ No newline at end of file
+pub use queue_withdrawal::*;
+pub use execute_withdrawal::*;
+pub use cancel_withdrawal::*;
+pub use update_config::*;
+pub use open_side_pool::*;
+pub use place_side_bet::*;
+pub use claim_side_bet::*;
+pub use enqueue::*;
+pub use dequeue::*;
+pub use pair::*;
+pub use reveal_colors::*;
+pub use claim_reveal_timeout::*;
+pub use start_game::*;
+pub use submit_move::*;
+pub use settle_game::*;
+pub use close_game_log::*;
+pub use claim_flag::*;
+pub use submit_channel_state::*;
+pub use challenge_channel_state::*;
+pub use settle_channel::*;
+pub use settle_with_attestation::*;
+pub use report_result::*;
+pub use propose_result::*;
+pub use dispute_result::*;
+pub use finalize_result::*;
+pub use resolve_dispute::*;
+pub use create_arbiter_set::*;
+pub use update_arbiter_set::*;
+pub use escalate_dispute::*;
+pub use cast_vote::*;
+pub use expire_dispute_case::*;
+pub use bond_oracle::*;
+pub use request_unbond::*;
+pub use withdraw_oracle_bond::*;
+pub use slash_oracle::*;
+pub use create_proposal::*;
+pub use approve_proposal::*;
+pub use execute_proposal::*;
+pub use cancel_proposal::*;
+pub use grant_role::*;
+pub use revoke_role::*;
+pub use set_paused::*;
+pub use update_fees::*;
+pub use create_fee_split::*;
+pub use update_fee_split::*;
+pub use distribute_fees::*;
+pub use register_referrer::*;
+pub use claim_referral_rewards::*;
+pub use close_side_pool::*;
+pub use expire_channel_claim::*;
+pub use init_stats::*;
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::transfer;
+use anchor_lang::system_program::Transfer;
+
+use crate::MatchConfig;
+use crate::MatchState;
+use crate::SidePool;
+use crate::SidePoolStatus;
+use crate::Status::*;
+
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+#[instruction(code: String)]
+pub struct OpenSidePool<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"match", match_account.seed.to_le_bytes().as_ref(), code.as_bytes(), match_account.player_a.key().as_ref()],
+        bump = match_account.bump,
+    )]
+    pub match_account: Account<'info, MatchState>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + SidePool::INIT_SPACE,
+        seeds = [b"side_pool", match_account.key().as_ref()],
+        bump,
+    )]
+    pub side_pool: Account<'info, SidePool>,
+
+    #[account(
+        mut,        //SystemAccount don't need to be initialized
+        seeds = [b"side_vault", side_pool.key().as_ref()],
+        bump
+    )]
+    pub side_vault: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump,
+        constraint = !config.paused @ ErrorCode::PausedError
+    )]
+    pub config: Account<'info, MatchConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> OpenSidePool<'info> {
+    pub fn open_side_pool(&mut self, _code: String, bumps: &OpenSidePoolBumps) -> Result<()> {
+        require!(
+            self.match_account.status == InProgress,
+            ErrorCode::InvalidMatchError
+        );
+
+        self.side_pool.set_inner(SidePool {
+            match_account: self.match_account.key(),
+            player_a: self.match_account.player_a,
+            player_b: self.match_account.player_b.ok_or(ErrorCode::InvalidMatchError)?,
+            payer: self.payer.key(),
+            closes_at: self
+                .match_account
+                .accepted_at
+                .checked_add(self.config.side_bet_cutoff)
+                .ok_or(ErrorCode::MathOverflow)?,
+            totals: [0; 3],
+            result: None,
+            payout_pool: 0,
+            open_bets: 0,
+            status: SidePoolStatus::Open,
+            bump: bumps.side_pool,
+            vault_bump: bumps.side_vault,
+        });
+        self.match_account.has_side_pool = true;
+
+        // Keep the vault rent exempt so the last claims can never leave it with a dangling balance
+        let rent_min = Rent::get()?.minimum_balance(0);
+
+        let transfer_accounts = Transfer {
+            from: self.payer.to_account_info(),
+            to: self.side_vault.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), transfer_accounts);
+
+        transfer(cpi_ctx, rent_min)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,174 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::transfer;
+use anchor_lang::system_program::Transfer;
+
+use crate::MatchConfig;
+use crate::MatchState;
+use crate::ProtocolStats;
+use crate::QueueTicket;
+use crate::Status::*;
+use crate::Variant;
+
+use crate::error::ErrorCode;
+
+/// Permissionless crank turning two compatible queue tickets into an `InProgress` match.
+/// Queued matches have an empty code, so their PDA is ("match", seed, "", player_a).
+/// Both tickets carry a colour commitment: player B reveals first, then player A as after accept_match,
+/// so neither the cranker's seed nor either player picks the colours.
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct Pair<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    #[account(mut)]
+    pub player_a: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub player_b: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ticket", player_a.key().as_ref(), ticket_a.bet_amount.to_le_bytes().as_ref(), ticket_a.time_control.to_seed_bytes().as_ref()],
+        bump = ticket_a.bump,
+        constraint = ticket_a.player == player_a.key() @ ErrorCode::InvalidPlayerError,
+        close = player_a
+    )]
+    pub ticket_a: Account<'info, QueueTicket>,
+
+    #[account(
+        mut,
+        seeds = [b"ticket_vault", ticket_a.key().as_ref()],
+        bump = ticket_a.vault_bump,
+    )]
+    pub ticket_vault_a: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ticket", player_b.key().as_ref(), ticket_b.bet_amount.to_le_bytes().as_ref(), ticket_b.time_control.to_seed_bytes().as_ref()],
+        bump = ticket_b.bump,
+        constraint = ticket_b.player == player_b.key() @ ErrorCode::InvalidPlayerError,
+        close = player_b
+    )]
+    pub ticket_b: Account<'info, QueueTicket>,
+
+    #[account(
+        mut,
+        seeds = [b"ticket_vault", ticket_b.key().as_ref()],
+        bump = ticket_b.vault_bump,
+    )]
+    pub ticket_vault_b: SystemAccount<'info>,
+
+    #[account(
+        init,
+        payer = cranker,
+        space = 8 + MatchState::INIT_SPACE,
+        seeds = [b"match", seed.to_le_bytes().as_ref(), b"".as_ref(), player_a.key().as_ref()],
+        bump,
+    )]
+    pub match_account: Account<'info, MatchState>,
+
+    #[account(
+        mut,        //SystemAccount don't need to be initialized
+        seeds = [b"vault", match_account.key().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump,
+        constraint = !config.paused @ ErrorCode::PausedError
+    )]
+    pub config: Account<'info, MatchConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"stats", [stats.shard].as_ref()],
+        bump = stats.bump,
+    )]
+    pub stats: Account<'info, ProtocolStats>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Pair<'info> {
+    pub fn pair(&mut self, seed: u64, bumps: &PairBumps) -> Result<()> {
+        require_keys_neq!(
+            self.player_a.key(),
+            self.player_b.key(),
+            ErrorCode::SamePlayerError
+        );
+        require!(
+            self.ticket_a.bet_amount == self.ticket_b.bet_amount
+                && self.ticket_a.time_control == self.ticket_b.time_control,
+            ErrorCode::IncompatibleTicketsError
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+
+        self.match_account.set_inner(MatchState {
+            seed,
+            bet_amount: self.ticket_a.bet_amount,
+            time_control: self.ticket_a.time_control,
+            variant: Variant::Standard,
+            start_position: None,
+            color_commitment: self.ticket_a.color_commitment,
+            player_b_commitment: Some(self.ticket_b.color_commitment),
+            player_b_random: [0; 32],
+            white: None,
+            player_a: self.player_a.key(),
+            player_b: Some(self.player_b.key()),
+            referrer_a: None, // matchmaking doesn't take referrers
+            referrer_b: None,
+            payer: self.cranker.key(),
+            created_at: now,
+            accepted_at: now,
+            reveal_window_start: now,
+            winner: None,
+            result_reason: None,
+            status: InProgress,
+            has_side_pool: false,
+            has_game_log: false,
+            reported_a: None,
+            reported_b: None,
+            settlement_nonce: Clock::get()?.slot,
+            channel_nonce: 0,
+            bump: bumps.match_account,
+            vault_bump: bumps.vault,
+        });
+
+        self.move_stake(&self.ticket_a, &self.ticket_vault_a)?;
+        self.move_stake(&self.ticket_b, &self.ticket_vault_b)?;
+
+        // a paired match is created and accepted in one go
+        let bet = self.match_account.bet_amount;
+        ProtocolStats::add(&mut self.stats.matches_created, 1);
+        ProtocolStats::add(&mut self.stats.matches_accepted, 1);
+        ProtocolStats::add(&mut self.stats.total_wagered, bet.saturating_mul(2));
+
+        Ok(())
+    }
+
+    fn move_stake(&self, ticket: &Account<'info, QueueTicket>, ticket_vault: &SystemAccount<'info>) -> Result<()> {
+        let transfer_accounts = Transfer {
+            from: ticket_vault.to_account_info(),
+            to: self.vault.to_account_info(),
+        };
+
+        let signer_seeds: &[&[&[u8]]; 1] = &[&[
+            b"ticket_vault",
+            ticket.to_account_info().key.as_ref(),
+            &[ticket.vault_bump],
+        ]];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.system_program.to_account_info(),
+            transfer_accounts,
+            signer_seeds,
+        );
+
+        transfer(cpi_ctx, ticket.bet_amount)?;
+        Ok(())
+    }
+}
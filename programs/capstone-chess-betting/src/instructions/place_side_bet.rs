@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::transfer;
+use anchor_lang::system_program::Transfer;
+
+use crate::MatchState;
+use crate::SideBet;
+use crate::SideOutcome;
+use crate::SidePool;
+use crate::SidePoolStatus;
+use crate::Status::*;
+
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct PlaceSideBet<'info> {
+    #[account(mut)]
+    pub bettor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"side_pool", side_pool.match_account.as_ref()],
+        bump = side_pool.bump,
+    )]
+    pub side_pool: Account<'info, SidePool>,
+
+    #[account(address = side_pool.match_account)]
+    pub match_account: Account<'info, MatchState>,
+
+    #[account(
+        init,
+        payer = bettor,
+        space = 8 + SideBet::INIT_SPACE,
+        seeds = [b"side_bet", side_pool.key().as_ref(), bettor.key().as_ref()],
+        bump,
+    )]
+    pub side_bet: Account<'info, SideBet>,
+
+    #[account(
+        mut,
+        seeds = [b"side_vault", side_pool.key().as_ref()],
+        bump = side_pool.vault_bump,
+    )]
+    pub side_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> PlaceSideBet<'info> {
+    pub fn place_side_bet(&mut self, outcome: SideOutcome, amount: u64, bumps: &PlaceSideBetBumps) -> Result<()> {
+        require!(
+            self.side_pool.status == SidePoolStatus::Open
+                && Clock::get()?.unix_timestamp < self.side_pool.closes_at,
+            ErrorCode::SidePoolClosed
+        );
+        // No bets once a result is out in the open, whether proposed, claimed over a channel or reported
+        require!(
+            self.match_account.status == InProgress
+                && self.match_account.reported_a.is_none()
+                && self.match_account.reported_b.is_none(),
+            ErrorCode::SidePoolClosed
+        );
+        require!(
+            self.bettor.key() != self.side_pool.player_a && self.bettor.key() != self.side_pool.player_b,
+            ErrorCode::PlayerSideBetError
+        );
+        require!(amount > 0, ErrorCode::InvalidBetAmount);
+
+        self.side_bet.set_inner(SideBet {
+            side_pool: self.side_pool.key(),
+            bettor: self.bettor.key(),
+            outcome,
+            amount,
+            bump: bumps.side_bet,
+        });
+
+        let bucket = &mut self.side_pool.totals[outcome as usize];
+        *bucket = bucket.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+        self.side_pool.open_bets = self.side_pool.open_bets.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+
+        let transfer_accounts = Transfer {
+            from: self.bettor.to_account_info(),
+            to: self.side_vault.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), transfer_accounts);
+
+        transfer(cpi_ctx, amount)?;
+
+        Ok(())
+    }
+}
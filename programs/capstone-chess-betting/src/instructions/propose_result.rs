@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+
+use crate::bond::proposer_bond;
+
+use crate::GameLog;
+use crate::MatchConfig;
+use crate::MatchState;
+use crate::OracleBond;
+use crate::RoleAssignment;
+use crate::ResultProposal;
+use crate::ResultReason;
+use crate::Status::*;
+
+use crate::error::ErrorCode;
+
+/// First phase of a correctable settlement: the result is recorded and can be disputed until the deadline.
+#[derive(Accounts)]
+#[instruction(code: String)]
+pub struct ProposeResult<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"match", match_account.seed.to_le_bytes().as_ref(), code.as_bytes(), match_account.player_a.key().as_ref()],
+        bump = match_account.bump,
+    )]
+    pub match_account: Account<'info, MatchState>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + ResultProposal::INIT_SPACE,
+        seeds = [b"proposal", match_account.key().as_ref()],
+        bump,
+    )]
+    pub proposal: Account<'info, ResultProposal>,
+
+    #[account(
+        seeds = [b"game_log", match_account.key().as_ref()],
+        bump = game_log.bump,
+    )]
+    pub game_log: Option<Account<'info, GameLog>>,
+
+    #[account(
+        seeds = [b"bond_vault", proposal.key().as_ref()],
+        bump,
+    )]
+    pub bond_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"oracle_bond", proposer.key().as_ref()],
+        bump = proposer_bond.bump,
+    )]
+    pub proposer_bond: Option<Account<'info, OracleBond>>,
+
+    // the oracle role of whoever the result comes from, not needed for the admin
+    pub oracle_role: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump
+    )]
+    pub config: Account<'info, MatchConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ProposeResult<'info> {
+    pub fn propose_result(
+        &mut self,
+        _code: String,
+        winner: Option<Pubkey>,
+        reason: ResultReason,
+        bumps: &ProposeResultBumps,
+    ) -> Result<()> {
+        OracleBond::require_bonded(&self.config, &self.proposer.key(), &self.proposer_bond, &self.oracle_role)?;
+        require!(
+            self.match_account.status == InProgress || self.match_account.status == Disputed,
+            ErrorCode::InvalidMatchError
+        );
+        if let Some(winner) = winner {
+            require!(
+                winner == self.match_account.player_a || Some(winner) == self.match_account.player_b,
+                ErrorCode::InvalidWinnerError
+            );
+        }
+        // moves stop while the proposal is open, so a result the board agrees with now can always be finalized
+        self.match_account.require_game_log_result(&self.game_log, winner)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        self.proposal.set_inner(ResultProposal {
+            match_account: self.match_account.key(),
+            proposer: self.proposer.key(),
+            winner,
+            reason,
+            challenge_deadline: now
+                .checked_add(self.config.dispute_window)
+                .ok_or(ErrorCode::MathOverflow)?,
+            disputer: None,
+            dispute_bond: 0,
+            escalated: false,
+            escalation_used: false,
+            bump: bumps.proposal,
+            bond_vault_bump: bumps.bond_vault,
+        });
+        self.match_account.status = Proposed;
+
+        // an oracle's bond can't be withdrawn while its proposals might still be overturned
+        if let Some(bond) = proposer_bond(&self.config, &self.proposer.key(), &mut self.proposer_bond)? {
+            bond.open_proposals += 1;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+
+use crate::MatchConfig;
+use crate::PendingWithdrawal;
+use crate::Role;
+use crate::RoleAssignment;
+use crate::error::ErrorCode;
+
+/// Schedules a treasury withdrawal, it can only be executed after the configured delay.
+#[derive(Accounts)]
+pub struct QueueWithdrawal<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.config_bump,
+        constraint = config.has_role(Role::Treasurer, &authority.key(), &role_assignment) @ ErrorCode::MissingRoleError
+    )]
+    pub config: Account<'info, MatchConfig>,
+
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PendingWithdrawal::INIT_SPACE,
+        seeds = [b"withdrawal", config.withdrawal_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub withdrawal: Account<'info, PendingWithdrawal>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> QueueWithdrawal<'info> {
+    pub fn queue_withdrawal(
+        &mut self,
+        amount: u64,
+        destination: Pubkey,
+        bumps: &QueueWithdrawalBumps,
+    ) -> Result<()> {
+        require!(amount > 0, ErrorCode::InsufficientBalance);
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            self.config.is_withdraw_destination(&destination, now),
+            ErrorCode::InvalidDestinationError
+        );
+
+        let eta = now
+            .checked_add(self.config.withdrawal_delay)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        self.withdrawal.set_inner(PendingWithdrawal {
+            id: self.config.withdrawal_count,
+            queued_by: self.authority.key(),
+            destination,
+            amount,
+            eta,
+            bump: bumps.withdrawal,
+        });
+        self.config.withdrawal_count += 1;
+
+        Ok(())
+    }
+}
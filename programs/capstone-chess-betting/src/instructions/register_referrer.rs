@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::ReferrerRewards;
+
+/// Opens the account a referrer's fee rebates accumulate in, players can only name registered referrers.
+#[derive(Accounts)]
+pub struct RegisterReferrer<'info> {
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = referrer,
+        space = 8 + ReferrerRewards::INIT_SPACE,
+        seeds = [b"referrer_rewards", referrer.key().as_ref()],
+        bump,
+    )]
+    pub referrer_rewards: Account<'info, ReferrerRewards>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RegisterReferrer<'info> {
+    pub fn register_referrer(&mut self, bumps: &RegisterReferrerBumps) -> Result<()> {
+        self.referrer_rewards.set_inner(ReferrerRewards {
+            referrer: self.referrer.key(),
+            claimable: 0,
+            total_earned: 0,
+            bump: bumps.referrer_rewards,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::OracleBond;
+
+use crate::error::ErrorCode;
+
+/// Starts the cooldown, the oracle stops being eligible to settle right away.
+#[derive(Accounts)]
+pub struct RequestUnbond<'info> {
+    pub oracle: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"oracle_bond", oracle.key().as_ref()],
+        bump = oracle_bond.bump,
+        has_one = oracle
+    )]
+    pub oracle_bond: Account<'info, OracleBond>,
+}
+
+impl<'info> RequestUnbond<'info> {
+    pub fn request_unbond(&mut self) -> Result<()> {
+        require!(
+            self.oracle_bond.unbond_requested_at == 0,
+            ErrorCode::AlreadyUnbondingError
+        );
+
+        self.oracle_bond.unbond_requested_at = Clock::get()?.unix_timestamp;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+use crate::MatchState;
+use crate::Status::*;
+use crate::Variant;
+use crate::CHESS960_POSITIONS;
+
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+#[instruction(code: String)]
+pub struct RevealColors<'info> {
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"match", match_account.seed.to_le_bytes().as_ref(), code.as_bytes(), match_account.player_a.key().as_ref()],
+        bump = match_account.bump,
+    )]
+    pub match_account: Account<'info, MatchState>,
+}
+
+impl<'info> RevealColors<'info> {
+    pub fn reveal_colors(&mut self, _code: String, secret: [u8; 32]) -> Result<()> {
+        require!(
+            self.match_account.status == InProgress,
+            ErrorCode::InvalidMatchError
+        );
+        require!(
+            self.match_account.white.is_none(),
+            ErrorCode::ColorsAlreadyAssignedError
+        );
+
+        // on a paired match player B committed too, and has to reveal before player A
+        if let Some(commitment) = self.match_account.player_b_commitment {
+            require!(
+                Some(self.player.key()) == self.match_account.player_b,
+                ErrorCode::InvalidPlayerError
+            );
+            require!(
+                hashv(&[&secret]).to_bytes() == commitment,
+                ErrorCode::InvalidRevealError
+            );
+
+            self.match_account.player_b_random = secret;
+            self.match_account.player_b_commitment = None;
+            self.match_account.reveal_window_start = Clock::get()?.unix_timestamp;
+            return Ok(());
+        }
+
+        require_keys_eq!(
+            self.player.key(),
+            self.match_account.player_a,
+            ErrorCode::InvalidPlayerError
+        );
+        require!(
+            hashv(&[&secret]).to_bytes() == self.match_account.color_commitment,
+            ErrorCode::InvalidRevealError
+        );
+
+        // player A fixed the secret before seeing player B's value, so neither controls the coin
+        // or the Chess960 position, which are read from different bytes of the same hash
+        let seed = hashv(&[&secret, &self.match_account.player_b_random]).to_bytes();
+        self.match_account.white = if seed[0] & 1 == 0 {
+            Some(self.match_account.player_a)
+        } else {
+            self.match_account.player_b
+        };
+        if self.match_account.variant == Variant::Chess960 {
+            let value = u64::from_le_bytes(seed[8..16].try_into().unwrap());
+            self.match_account.start_position = Some((value % CHESS960_POSITIONS as u64) as u16);
+        }
+
+        Ok(())
+    }
+}
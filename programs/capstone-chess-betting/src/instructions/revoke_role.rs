@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::MatchConfig;
+use crate::Role;
+use crate::RoleAssignment;
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct RevokeRole<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump,
+        constraint = config.has_role(Role::Admin, &authority.key(), &role_assignment) @ ErrorCode::InvalidAdminError
+    )]
+    pub config: Account<'info, MatchConfig>,
+
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        mut,
+        seeds = [b"role", [revoked.role as u8].as_ref(), revoked.holder.as_ref()],
+        bump = revoked.bump,
+        has_one = granted_by,
+        close = granted_by
+    )]
+    pub revoked: Account<'info, RoleAssignment>,
+
+    #[account(mut)]
+    pub granted_by: SystemAccount<'info>,
+}
+
+impl<'info> RevokeRole<'info> {
+    pub fn revoke_role(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
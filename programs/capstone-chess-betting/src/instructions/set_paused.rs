@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::MatchConfig;
+use crate::Role;
+use crate::RoleAssignment;
+use crate::error::ErrorCode;
+
+/// Stops or resumes new matches and side pools, running matches can still settle.
+/// A pauser can only pause, resuming takes the admin role.
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.config_bump,
+    )]
+    pub config: Account<'info, MatchConfig>,
+
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+}
+
+impl<'info> SetPaused<'info> {
+    pub fn set_paused(&mut self, paused: bool) -> Result<()> {
+        let role = if paused { Role::Pauser } else { Role::Admin };
+        require!(
+            self.config.has_role(role, &self.authority.key(), &self.role_assignment),
+            ErrorCode::MissingRoleError
+        );
+
+        self.config.paused = paused;
+
+        Ok(())
+    }
+}
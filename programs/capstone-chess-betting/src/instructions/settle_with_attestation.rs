@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions;
+
+use crate::ed25519::verify_signature;
+use crate::payout::Payout;
+use crate::Attestation;
+use crate::GameLog;
+use crate::MatchConfig;
+use crate::MatchState;
+use crate::ProtocolStats;
+use crate::OracleBond;
+use crate::ReferrerRewards;
+use crate::RoleAssignment;
+use crate::SidePool;
+
+use crate::error::ErrorCode;
+
+/// Settles a match from a result a registered oracle signed, so players or a relayer can submit it.
+/// The oracle's signature over the match's settlement nonce is the authorization, instant settlement needn't be on.
+#[derive(Accounts)]
+#[instruction(code: String)]
+pub struct SettleWithAttestation<'info> {
+    pub relayer: Signer<'info>,
+
+    #[account(mut)]
+    pub player_a: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub player_b: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"match", match_account.seed.to_le_bytes().as_ref(), code.as_bytes(), match_account.player_a.key().as_ref()],
+        bump = match_account.bump,
+        has_one = payer,
+        close = payer
+    )]
+    pub match_account: Account<'info, MatchState>,
+
+    #[account(mut)]
+    pub payer: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", match_account.key().as_ref()],
+        bump = match_account.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"game_log", match_account.key().as_ref()],
+        bump = game_log.bump,
+    )]
+    pub game_log: Option<Account<'info, GameLog>>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump
+    )]
+    pub config: Account<'info, MatchConfig>,
+
+    #[account(
+        seeds = [b"oracle_bond", oracle_bond.oracle.as_ref()],
+        bump = oracle_bond.bump,
+    )]
+    pub oracle_bond: Option<Account<'info, OracleBond>>,
+
+    // the oracle role of whoever the result comes from, not needed for the admin
+    pub oracle_role: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        mut,        //SystemAccount don't need to be initialized
+        seeds = [b"treasury"],
+        bump = config.treasury_bump
+    )]
+    pub treasury_pda: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"side_pool", match_account.key().as_ref()],
+        bump = side_pool.bump,
+    )]
+    pub side_pool: Option<Account<'info, SidePool>>,
+
+    #[account(
+        mut,
+        seeds = [b"side_vault", side_pool.as_ref().ok_or(ErrorCode::InvalidSidePool)?.key().as_ref()],
+        bump = side_pool.as_ref().ok_or(ErrorCode::InvalidSidePool)?.vault_bump,
+    )]
+    pub side_vault: Option<SystemAccount<'info>>,
+
+    /// CHECK: the instructions sysvar, read for the oracle's Ed25519 verification
+    #[account(address = instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    // needed for each player with a referrer on the match
+    #[account(
+        mut,
+        seeds = [b"referrer_rewards", referrer_rewards_a.referrer.as_ref()],
+        bump = referrer_rewards_a.bump,
+    )]
+    pub referrer_rewards_a: Option<Account<'info, ReferrerRewards>>,
+
+    #[account(
+        mut,
+        seeds = [b"referrer_rewards", referrer_rewards_b.referrer.as_ref()],
+        bump = referrer_rewards_b.bump,
+    )]
+    pub referrer_rewards_b: Option<Account<'info, ReferrerRewards>>,
+
+    #[account(
+        mut,
+        seeds = [b"stats", [stats.shard].as_ref()],
+        bump = stats.bump,
+    )]
+    pub stats: Account<'info, ProtocolStats>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SettleWithAttestation<'info> {
+    pub fn settle_with_attestation(
+        &mut self,
+        _code: String,
+        oracle: Pubkey,
+        attestation: Attestation,
+    ) -> Result<()> {
+        OracleBond::require_bonded(&self.config, &oracle, &self.oracle_bond, &self.oracle_role)?;
+        require_keys_eq!(
+            attestation.match_account,
+            self.match_account.key(),
+            ErrorCode::InvalidMatchError
+        );
+        require!(
+            attestation.nonce == self.match_account.settlement_nonce,
+            ErrorCode::InvalidNonceError
+        );
+        verify_signature(&self.instructions, &oracle, &attestation.message()?)?;
+
+        self.payout().settle(attestation.winner, attestation.reason)
+    }
+
+    fn payout(&mut self) -> Payout<'_, 'info> {
+        Payout {
+            player_a: self.player_a.to_account_info(),
+            player_b: self.player_b.to_account_info(),
+            match_account: &mut self.match_account,
+            vault: &self.vault,
+            game_log: &self.game_log,
+            config: &self.config,
+            treasury_pda: &self.treasury_pda,
+            side_pool: &mut self.side_pool,
+            side_vault: &self.side_vault,
+            stats: &mut self.stats,
+            referrer_rewards: [self.referrer_rewards_a.as_mut(), self.referrer_rewards_b.as_mut()],
+            system_program: &self.system_program,
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::bond::slash_oracle_bond;
+use crate::MatchConfig;
+use crate::OracleBond;
+use crate::Role;
+use crate::RoleAssignment;
+
+use crate::error::ErrorCode;
+
+/// Admin compensation of a wronged player out of an oracle's bond.
+#[derive(Accounts)]
+pub struct SlashOracle<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump,
+        constraint = config.has_role(Role::Admin, &authority.key(), &role_assignment) @ ErrorCode::InvalidAdminError
+    )]
+    pub config: Account<'info, MatchConfig>,
+
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        mut,
+        seeds = [b"oracle_bond", oracle_bond.oracle.as_ref()],
+        bump = oracle_bond.bump,
+    )]
+    pub oracle_bond: Account<'info, OracleBond>,
+
+    #[account(
+        mut,
+        seeds = [b"oracle_bond_vault", oracle_bond.key().as_ref()],
+        bump = oracle_bond.vault_bump,
+    )]
+    pub bond_vault: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub recipient: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SlashOracle<'info> {
+    pub fn slash_oracle(&mut self, amount: u64) -> Result<()> {
+        slash_oracle_bond(
+            &mut self.oracle_bond,
+            &self.bond_vault,
+            self.recipient.to_account_info(),
+            amount,
+            &self.system_program,
+        )?;
+
+        Ok(())
+    }
+}
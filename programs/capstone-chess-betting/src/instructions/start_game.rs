@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+use crate::chess::Board;
+use crate::BoardStatus;
+use crate::GameLog;
+use crate::MatchState;
+use crate::Status::*;
+use crate::Variant;
+
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+#[instruction(code: String)]
+pub struct StartGame<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"match", match_account.seed.to_le_bytes().as_ref(), code.as_bytes(), match_account.player_a.key().as_ref()],
+        bump = match_account.bump,
+    )]
+    pub match_account: Account<'info, MatchState>,
+
+    #[account(
+        init,
+        payer = player,
+        space = 8 + GameLog::INIT_SPACE,
+        seeds = [b"game_log", match_account.key().as_ref()],
+        bump,
+    )]
+    pub game_log: Account<'info, GameLog>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> StartGame<'info> {
+    pub fn start_game(&mut self, _code: String, bumps: &StartGameBumps) -> Result<()> {
+        require!(
+            self.match_account.status == InProgress,
+            ErrorCode::InvalidMatchError
+        );
+        require!(
+            self.player.key() == self.match_account.player_a
+                || Some(self.player.key()) == self.match_account.player_b,
+            ErrorCode::InvalidPlayerError
+        );
+        require!(
+            self.match_account.white.is_some(),
+            ErrorCode::ColorsNotAssignedError
+        );
+        require!(
+            self.match_account.variant == Variant::Standard,
+            ErrorCode::UnsupportedVariantError
+        );
+
+        let board = Board::start_position();
+        let now = Clock::get()?.unix_timestamp;
+        let base = self.match_account.time_control.base_seconds as i64;
+
+        self.game_log.set_inner(GameLog {
+            match_account: self.match_account.key(),
+            payer: self.player.key(),
+            board: board.pack(),
+            started_at: now,
+            last_move_at: now,
+            white_remaining: base,
+            black_remaining: base,
+            hash_chain: GameLog::genesis_hash(&self.match_account.key(), &board),
+            moves: Vec::new(),
+            move_times: Vec::new(),
+            position_hashes: vec![board.hash()],
+            status: BoardStatus::Ongoing,
+            winner: None,
+            bump: bumps.game_log,
+        });
+        self.match_account.has_game_log = true;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions;
+
+use crate::ChannelClaim;
+use crate::ChannelState;
+use crate::MatchConfig;
+use crate::MatchState;
+use crate::Status::*;
+
+use crate::error::ErrorCode;
+
+/// Starts settling an off-chain game with a state both players signed, opening the challenge window.
+/// The match is held in `ChannelPending` until the claim is settled or expires.
+#[derive(Accounts)]
+#[instruction(code: String)]
+pub struct SubmitChannelState<'info> {
+    #[account(mut)]
+    pub submitter: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"match", match_account.seed.to_le_bytes().as_ref(), code.as_bytes(), match_account.player_a.key().as_ref()],
+        bump = match_account.bump,
+    )]
+    pub match_account: Account<'info, MatchState>,
+
+    #[account(
+        init,
+        payer = submitter,
+        space = 8 + ChannelClaim::INIT_SPACE,
+        seeds = [b"channel", match_account.key().as_ref()],
+        bump,
+    )]
+    pub channel_claim: Account<'info, ChannelClaim>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump
+    )]
+    pub config: Account<'info, MatchConfig>,
+
+    /// CHECK: the instructions sysvar, read for the Ed25519 verifications
+    #[account(address = instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SubmitChannelState<'info> {
+    pub fn submit_channel_state(
+        &mut self,
+        _code: String,
+        state: ChannelState,
+        bumps: &SubmitChannelStateBumps,
+    ) -> Result<()> {
+        require!(
+            self.match_account.status == InProgress,
+            ErrorCode::InvalidMatchError
+        );
+        require!(
+            state.nonce > self.match_account.channel_nonce,
+            ErrorCode::StaleChannelStateError
+        );
+        state.verify(&self.instructions, self.match_account.key(), &self.match_account)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        self.channel_claim.set_inner(ChannelClaim {
+            match_account: self.match_account.key(),
+            submitter: self.submitter.key(),
+            nonce: state.nonce,
+            board_hash: state.board_hash,
+            result: state.result,
+            reason: state.reason,
+            challenge_deadline: now
+                .checked_add(self.config.challenge_window)
+                .ok_or(ErrorCode::MathOverflow)?,
+            bump: bumps.channel_claim,
+        });
+        self.match_account.status = ChannelPending;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+
+use crate::chess::{Board, Color, GameStatus, Move};
+use crate::GameLog;
+use crate::MatchState;
+use crate::Status::*;
+use crate::MAX_PLIES;
+
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+#[instruction(code: String)]
+pub struct SubmitMove<'info> {
+    pub player: Signer<'info>,
+
+    #[account(
+        seeds = [b"match", match_account.seed.to_le_bytes().as_ref(), code.as_bytes(), match_account.player_a.key().as_ref()],
+        bump = match_account.bump,
+    )]
+    pub match_account: Account<'info, MatchState>,
+
+    #[account(
+        mut,
+        seeds = [b"game_log", match_account.key().as_ref()],
+        bump = game_log.bump,
+    )]
+    pub game_log: Account<'info, GameLog>,
+}
+
+impl<'info> SubmitMove<'info> {
+    pub fn submit_move(&mut self, _code: String, packed_move: u16) -> Result<()> {
+        require!(
+            self.match_account.status == InProgress,
+            ErrorCode::InvalidMatchError
+        );
+        require!(!self.game_log.is_finished(), ErrorCode::GameFinishedError);
+        require!(
+            self.game_log.moves.len() < MAX_PLIES,
+            ErrorCode::GameLogFullError
+        );
+
+        let mut board = Board::unpack(&self.game_log.board);
+        let mover = self.player_for(board.side_to_move);
+        require!(
+            mover == Some(self.player.key()),
+            ErrorCode::NotYourTurnError
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let time_control = self.match_account.time_control;
+        require!(
+            !self.game_log.has_flagged(board.side_to_move, &time_control, now),
+            ErrorCode::FlagFallenError
+        );
+
+        let mv = Move::unpack(packed_move).ok_or(ErrorCode::IllegalMoveError)?;
+        require!(board.is_legal(mv), ErrorCode::IllegalMoveError);
+        self.game_log.charge_clock(board.side_to_move, &time_control, now);
+        board.make_move(mv);
+
+        self.game_log.record(mv, &board, now);
+
+        let earlier = self.game_log.position_hashes.len() - 1;
+        let status = board.status(&self.game_log.position_hashes[..earlier]);
+        self.game_log.status = status.into();
+        if let GameStatus::Checkmate { winner } = status {
+            self.game_log.winner = self.player_for(winner);
+        }
+
+        Ok(())
+    }
+
+    fn player_for(&self, color: Color) -> Option<Pubkey> {
+        match color {
+            Color::White => self.match_account.white,
+            Color::Black => self.match_account.black(),
+        }
+    }
+}
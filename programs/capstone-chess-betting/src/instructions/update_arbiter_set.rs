@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::ArbiterSet;
+use crate::MatchConfig;
+use crate::Role;
+use crate::RoleAssignment;
+
+use crate::error::ErrorCode;
+
+/// Replaces the panel, cases that are already open keep the arbiters they were assigned.
+#[derive(Accounts)]
+pub struct UpdateArbiterSet<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump,
+        constraint = config.has_role(Role::Admin, &authority.key(), &role_assignment) @ ErrorCode::InvalidAdminError
+    )]
+    pub config: Account<'info, MatchConfig>,
+
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        mut,
+        seeds = [b"arbiter_set"],
+        bump = arbiter_set.bump,
+    )]
+    pub arbiter_set: Account<'info, ArbiterSet>,
+}
+
+impl<'info> UpdateArbiterSet<'info> {
+    pub fn update_arbiter_set(
+        &mut self,
+        arbiters: Vec<Pubkey>,
+        threshold: u8,
+        arbiter_fee: u64,
+        vote_window: i64,
+    ) -> Result<()> {
+        self.arbiter_set.configure(arbiters, threshold, arbiter_fee, vote_window)
+    }
+}
@@ -0,0 +1,196 @@
+use anchor_lang::prelude::*;
+
+use crate::MatchConfig;
+use crate::Role;
+use crate::RoleAssignment;
+use crate::MAX_ADMIN_SIGNERS;
+use crate::WithdrawDestination;
+use crate::MAX_WITHDRAW_DESTINATIONS;
+use crate::MIN_WITHDRAWAL_DELAY;
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.config_bump,
+        constraint = config.has_role(Role::Admin, &authority.key(), &role_assignment) @ ErrorCode::InvalidAdminError
+    )]
+    pub config: Account<'info, MatchConfig>,
+
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+}
+
+impl<'info> UpdateConfig<'info> {
+    pub fn set_time_control_bounds(
+        &mut self,
+        min_base_seconds: u32,
+        max_base_seconds: u32,
+        max_increment_seconds: u32,
+        max_delay_seconds: u32,
+    ) -> Result<()> {
+        require!(
+            min_base_seconds <= max_base_seconds,
+            ErrorCode::InvalidConfigError
+        );
+
+        self.config.min_base_seconds = min_base_seconds;
+        self.config.max_base_seconds = max_base_seconds;
+        self.config.max_increment_seconds = max_increment_seconds;
+        self.config.max_delay_seconds = max_delay_seconds;
+
+        Ok(())
+    }
+
+    pub fn set_bet_limits(&mut self, min_bet: u64, max_bet: u64) -> Result<()> {
+        // the match vault holds no data, a stake below its rent minimum can't be deposited on its own
+        require!(
+            min_bet >= Rent::get()?.minimum_balance(0),
+            ErrorCode::InvalidConfigError
+        );
+        require!(min_bet <= max_bet, ErrorCode::InvalidConfigError);
+
+        self.config.min_bet = min_bet;
+        self.config.max_bet = max_bet;
+
+        Ok(())
+    }
+
+    pub fn set_reveal_window(&mut self, reveal_window: i64) -> Result<()> {
+        require!(reveal_window > 0, ErrorCode::InvalidConfigError);
+
+        self.config.reveal_window = reveal_window;
+
+        Ok(())
+    }
+
+    pub fn set_challenge_window(&mut self, challenge_window: i64) -> Result<()> {
+        require!(challenge_window > 0, ErrorCode::InvalidConfigError);
+
+        self.config.challenge_window = challenge_window;
+
+        Ok(())
+    }
+
+    pub fn set_dispute_params(&mut self, dispute_window: i64, dispute_bond: u64) -> Result<()> {
+        require!(dispute_window > 0, ErrorCode::InvalidConfigError);
+        // the bond vault holds no data, it must be funded past the rent minimum or not at all
+        require!(
+            dispute_bond >= Rent::get()?.minimum_balance(0),
+            ErrorCode::InvalidConfigError
+        );
+
+        self.config.dispute_window = dispute_window;
+        self.config.dispute_bond = dispute_bond;
+
+        Ok(())
+    }
+
+    pub fn set_oracle_bond_params(
+        &mut self,
+        min_oracle_bond: u64,
+        oracle_unbond_cooldown: i64,
+        oracle_slash_bps: u16,
+    ) -> Result<()> {
+        require!(oracle_unbond_cooldown >= 0, ErrorCode::InvalidConfigError);
+        require!(oracle_slash_bps <= 10_000, ErrorCode::InvalidFeeBps);
+
+        self.config.min_oracle_bond = min_oracle_bond;
+        self.config.oracle_unbond_cooldown = oracle_unbond_cooldown;
+        self.config.oracle_slash_bps = oracle_slash_bps;
+
+        Ok(())
+    }
+
+    pub fn set_instant_settlement(&mut self, instant_settlement: bool) -> Result<()> {
+        self.config.instant_settlement = instant_settlement;
+
+        Ok(())
+    }
+
+    pub fn set_withdrawal_params(
+        &mut self,
+        withdrawal_delay: i64,
+        withdrawal_epoch: i64,
+        withdrawal_epoch_cap: u64,
+    ) -> Result<()> {
+        require!(withdrawal_delay >= MIN_WITHDRAWAL_DELAY, ErrorCode::InvalidConfigError);
+        require!(withdrawal_epoch > 0, ErrorCode::InvalidConfigError);
+
+        // looser limits wait out the delay in force before this change
+        let now = Clock::get()?.unix_timestamp;
+        self.config.set_withdrawal_limits(withdrawal_epoch, withdrawal_epoch_cap, now)?;
+        self.config.withdrawal_delay = withdrawal_delay;
+
+        Ok(())
+    }
+
+    /// New destinations only become usable after the withdrawal delay.
+    pub fn add_withdraw_destination(&mut self, destination: Pubkey) -> Result<()> {
+        require!(
+            !self.config.withdraw_destinations.iter().any(|allowed| allowed.key == destination),
+            ErrorCode::InvalidConfigError
+        );
+        require!(
+            self.config.withdraw_destinations.len() < MAX_WITHDRAW_DESTINATIONS,
+            ErrorCode::InvalidConfigError
+        );
+
+        let active_at = Clock::get()?
+            .unix_timestamp
+            .checked_add(self.config.withdrawal_delay)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.config.withdraw_destinations.push(WithdrawDestination {
+            key: destination,
+            active_at,
+        });
+
+        Ok(())
+    }
+
+    pub fn remove_withdraw_destination(&mut self, destination: Pubkey) -> Result<()> {
+        let len = self.config.withdraw_destinations.len();
+        self.config.withdraw_destinations.retain(|allowed| allowed.key != destination);
+        require!(
+            self.config.withdraw_destinations.len() < len,
+            ErrorCode::InvalidDestinationError
+        );
+
+        Ok(())
+    }
+
+    /// Hands the admin role to an M-of-N signer set. The authority becomes the admin PDA,
+    /// so this and every other admin instruction has to go through a proposal from then on.
+    pub fn set_admin_signers(&mut self, signers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        // an admin role holder can't hand the authority away
+        require_keys_eq!(
+            self.authority.key(),
+            self.config.authority,
+            ErrorCode::InvalidAdminError
+        );
+        require!(
+            !signers.is_empty() && signers.len() <= MAX_ADMIN_SIGNERS,
+            ErrorCode::InvalidSignerSetError
+        );
+        require!(
+            signers.iter().enumerate().all(|(i, s)| !signers[..i].contains(s)),
+            ErrorCode::InvalidSignerSetError
+        );
+        require!(
+            threshold > 0 && (threshold as usize) <= signers.len(),
+            ErrorCode::InvalidSignerSetError
+        );
+
+        let (admin, admin_bump) = Pubkey::find_program_address(&[b"admin"], &crate::ID);
+
+        self.config.admin_signers = signers;
+        self.config.admin_threshold = threshold;
+        self.config.admin_bump = admin_bump;
+        self.config.authority = admin;
+
+        Ok(())
+    }
+}
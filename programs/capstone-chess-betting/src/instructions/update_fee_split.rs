@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::FeeShare;
+use crate::FeeSplit;
+use crate::MatchConfig;
+use crate::Role;
+use crate::RoleAssignment;
+
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct UpdateFeeSplit<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump,
+        constraint = config.has_role(Role::Admin, &authority.key(), &role_assignment) @ ErrorCode::InvalidAdminError
+    )]
+    pub config: Account<'info, MatchConfig>,
+
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_split"],
+        bump = fee_split.bump,
+    )]
+    pub fee_split: Account<'info, FeeSplit>,
+}
+
+impl<'info> UpdateFeeSplit<'info> {
+    pub fn update_fee_split(&mut self, shares: Vec<FeeShare>, reserve: u64) -> Result<()> {
+        self.fee_split.configure(shares, reserve)
+    }
+}
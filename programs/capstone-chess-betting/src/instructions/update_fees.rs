@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+
+use crate::MatchConfig;
+use crate::Role;
+use crate::RoleAssignment;
+use crate::error::ErrorCode;
+
+/// Fee setters, open to the fee manager role as well as the admin.
+#[derive(Accounts)]
+pub struct UpdateFees<'info> {
+    pub fee_manager: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.config_bump,
+        constraint = config.has_role(Role::FeeManager, &fee_manager.key(), &role_assignment) @ ErrorCode::MissingRoleError
+    )]
+    pub config: Account<'info, MatchConfig>,
+
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+}
+
+impl<'info> UpdateFees<'info> {
+    pub fn set_side_pool_params(&mut self, fee_bps: u16, cutoff: i64) -> Result<()> {
+        require!(fee_bps <= 10_000, ErrorCode::InvalidFeeBps);
+        require!(cutoff >= 0, ErrorCode::InvalidConfigError);
+
+        self.config.side_pool_fee_bps = fee_bps;
+        self.config.side_bet_cutoff = cutoff;
+
+        Ok(())
+    }
+
+    pub fn set_category_params(&mut self, fee_bps: [u16; 4], timeouts: [i64; 4]) -> Result<()> {
+        require!(
+            fee_bps.iter().all(|bps| *bps <= 10_000),
+            ErrorCode::InvalidFeeBps
+        );
+        require!(
+            timeouts.iter().all(|timeout| *timeout >= 0),
+            ErrorCode::InvalidConfigError
+        );
+
+        self.config.category_fee_bps = fee_bps;
+        self.config.category_timeouts = timeouts;
+
+        Ok(())
+    }
+
+    pub fn set_referral_fee_bps(&mut self, referral_fee_bps: u16) -> Result<()> {
+        require!(referral_fee_bps <= 10_000, ErrorCode::InvalidFeeBps);
+
+        self.config.referral_fee_bps = referral_fee_bps;
+
+        Ok(())
+    }
+
+    pub fn set_cancel_penalty_params(
+        &mut self,
+        cancel_penalty_bps: u16,
+        cancel_penalty_step: i64,
+        cancel_penalty_step_bps: u16,
+        max_cancel_penalty_bps: u16,
+        cancel_opponent_share_bps: u16,
+    ) -> Result<()> {
+        require!(
+            cancel_penalty_bps <= max_cancel_penalty_bps
+                && max_cancel_penalty_bps <= 10_000
+                && cancel_opponent_share_bps <= 10_000,
+            ErrorCode::InvalidFeeBps
+        );
+        require!(cancel_penalty_step > 0, ErrorCode::InvalidConfigError);
+
+        self.config.cancel_penalty_bps = cancel_penalty_bps;
+        self.config.cancel_penalty_step = cancel_penalty_step;
+        self.config.cancel_penalty_step_bps = cancel_penalty_step_bps;
+        self.config.max_cancel_penalty_bps = max_cancel_penalty_bps;
+        self.config.cancel_opponent_share_bps = cancel_opponent_share_bps;
+
+        Ok(())
+    }
+}
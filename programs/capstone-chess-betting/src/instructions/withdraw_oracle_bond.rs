@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::transfer;
+use anchor_lang::system_program::Transfer;
+
+use crate::MatchConfig;
+use crate::OracleBond;
+
+use crate::error::ErrorCode;
+
+/// Returns what is left of the bond once the cooldown has passed and no proposal can still be overturned.
+#[derive(Accounts)]
+pub struct WithdrawOracleBond<'info> {
+    #[account(mut)]
+    pub oracle: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"oracle_bond", oracle.key().as_ref()],
+        bump = oracle_bond.bump,
+        has_one = oracle,
+        close = oracle
+    )]
+    pub oracle_bond: Account<'info, OracleBond>,
+
+    #[account(
+        mut,
+        seeds = [b"oracle_bond_vault", oracle_bond.key().as_ref()],
+        bump = oracle_bond.vault_bump,
+    )]
+    pub bond_vault: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump
+    )]
+    pub config: Account<'info, MatchConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> WithdrawOracleBond<'info> {
+    pub fn withdraw_oracle_bond(&mut self) -> Result<()> {
+        let requested_at = self.oracle_bond.unbond_requested_at;
+        require!(requested_at != 0, ErrorCode::UnbondCooldownError);
+        require!(
+            Clock::get()?.unix_timestamp >= requested_at.saturating_add(self.config.oracle_unbond_cooldown),
+            ErrorCode::UnbondCooldownError
+        );
+        require!(
+            self.oracle_bond.open_proposals == 0,
+            ErrorCode::UnbondCooldownError
+        );
+
+        let transfer_accounts = Transfer {
+            from: self.bond_vault.to_account_info(),
+            to: self.oracle.to_account_info(),
+        };
+
+        let signer_seeds: &[&[&[u8]]; 1] = &[&[
+            b"oracle_bond_vault",
+            self.oracle_bond.to_account_info().key.as_ref(),
+            &[self.oracle_bond.vault_bump],
+        ]];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.system_program.to_account_info(),
+            transfer_accounts,
+            signer_seeds,
+        );
+
+        transfer(cpi_ctx, self.bond_vault.lamports())
+    }
+}
@@ -1,8 +1,13 @@
 #![allow(deprecated)]
 #![allow(unexpected_cfgs)]
+pub mod bond;
+pub mod chess;
 pub mod constants;
+pub mod ed25519;
 pub mod error;
+pub mod events;
 pub mod instructions;
+pub mod payout;
 pub mod state;
 
 use anchor_lang::prelude::*;
@@ -17,22 +22,29 @@ declare_id!("3fsvNChoZg4zdod2Tfw8ApdPhgShnmpJDrfe1GPweHdm");
 pub mod capstone_chess_betting {
     use super::*;
 
+    #[allow(clippy::too_many_arguments)]
     pub fn initialize_match(
         ctx: Context<InitializeMatch>,
         seed: u64,
         code: String,
-        match_duration: u32,
+        time_control: TimeControl,
+        variant: Variant,
+        color_commitment: [u8; 32],
         bet_amount: u64,
         player_b: Option<Pubkey>,
         winner: Option<Pubkey>,
+        referrer: Option<Pubkey>,
     ) -> Result<()> {
         ctx.accounts.init_match(
             seed,
             code,
-            match_duration,
+            time_control,
+            variant,
+            color_commitment,
             bet_amount,
             player_b,
             winner,
+            referrer,
             &ctx.bumps,
         )?;
         ctx.accounts.deposit_bet()?;
@@ -47,8 +59,74 @@ pub mod capstone_chess_betting {
         Ok(())
     }
 
-    pub fn accept_match(ctx: Context<AcceptMatch>, code: String) -> Result<()>{
-        ctx.accounts.accept_match(code)?;
+    pub fn init_stats(ctx: Context<InitStats>, shard: u8) -> Result<()> {
+        ctx.accounts.init_stats(shard, &ctx.bumps)?;
+        Ok(())
+    }
+
+    pub fn accept_match(
+        ctx: Context<AcceptMatch>,
+        code: String,
+        expected_variant: Variant,
+        player_b_random: [u8; 32],
+        referrer: Option<Pubkey>,
+    ) -> Result<()>{
+        ctx.accounts.accept_match(code, expected_variant, player_b_random, referrer, &ctx.bumps)?;
+        Ok(())
+    }
+
+    pub fn reveal_colors(ctx: Context<RevealColors>, code: String, secret: [u8; 32]) -> Result<()> {
+        ctx.accounts.reveal_colors(code, secret)?;
+        Ok(())
+    }
+
+    pub fn claim_reveal_timeout(ctx: Context<ClaimRevealTimeout>, code: String) -> Result<()> {
+        ctx.accounts.claim_reveal_timeout(code)?;
+        Ok(())
+    }
+
+    pub fn start_game(ctx: Context<StartGame>, code: String) -> Result<()> {
+        ctx.accounts.start_game(code, &ctx.bumps)?;
+        Ok(())
+    }
+
+    pub fn submit_move(ctx: Context<SubmitMove>, code: String, packed_move: u16) -> Result<()> {
+        ctx.accounts.submit_move(code, packed_move)?;
+        Ok(())
+    }
+
+    pub fn settle_game(ctx: Context<SettleGame>, code: String) -> Result<()> {
+        ctx.accounts.settle_game(code)?;
+        Ok(())
+    }
+
+    pub fn claim_flag(ctx: Context<ClaimFlag>, code: String) -> Result<()> {
+        ctx.accounts.claim_flag(code)?;
+        Ok(())
+    }
+
+    pub fn submit_channel_state(ctx: Context<SubmitChannelState>, code: String, state: ChannelState) -> Result<()> {
+        ctx.accounts.submit_channel_state(code, state, &ctx.bumps)?;
+        Ok(())
+    }
+
+    pub fn challenge_channel_state(ctx: Context<ChallengeChannelState>, code: String, state: ChannelState) -> Result<()> {
+        ctx.accounts.challenge_channel_state(code, state)?;
+        Ok(())
+    }
+
+    pub fn settle_channel(ctx: Context<SettleChannel>, code: String) -> Result<()> {
+        ctx.accounts.settle_channel(code)?;
+        Ok(())
+    }
+
+    pub fn expire_channel_claim(ctx: Context<ExpireChannelClaim>, code: String) -> Result<()> {
+        ctx.accounts.expire_channel_claim(code)?;
+        Ok(())
+    }
+
+    pub fn close_game_log(ctx: Context<CloseGameLog>) -> Result<()> {
+        ctx.accounts.close_game_log()?;
         Ok(())
     }
 
@@ -57,13 +135,340 @@ pub mod capstone_chess_betting {
         Ok(())
     }
 
-    pub fn final_payouts(ctx: Context<FinalPayments>, code: String, winner_key: Option<Pubkey>) -> Result<()> {
-        ctx.accounts.final_payouts(code, winner_key)?;
+    pub fn final_payouts(
+        ctx: Context<FinalPayments>,
+        code: String,
+        winner_key: Option<Pubkey>,
+        reason: ResultReason,
+    ) -> Result<()> {
+        ctx.accounts.final_payouts(code, winner_key, reason)?;
+        Ok(())
+    }
+
+    pub fn settle_with_attestation(
+        ctx: Context<SettleWithAttestation>,
+        code: String,
+        oracle: Pubkey,
+        attestation: Attestation,
+    ) -> Result<()> {
+        ctx.accounts.settle_with_attestation(code, oracle, attestation)?;
+        Ok(())
+    }
+
+    pub fn report_result(ctx: Context<ReportResult>, code: String, report: ResultReport) -> Result<()> {
+        ctx.accounts.report_result(code, report)?;
+        Ok(())
+    }
+
+    pub fn propose_result(
+        ctx: Context<ProposeResult>,
+        code: String,
+        winner: Option<Pubkey>,
+        reason: ResultReason,
+    ) -> Result<()> {
+        ctx.accounts.propose_result(code, winner, reason, &ctx.bumps)?;
+        Ok(())
+    }
+
+    pub fn dispute_result(ctx: Context<DisputeResult>, code: String) -> Result<()> {
+        ctx.accounts.dispute_result(code)?;
+        Ok(())
+    }
+
+    pub fn finalize_result(ctx: Context<FinalizeResult>, code: String) -> Result<()> {
+        ctx.accounts.finalize_result(code)?;
+        Ok(())
+    }
+
+    pub fn resolve_dispute(
+        ctx: Context<ResolveDispute>,
+        code: String,
+        winner: Option<Pubkey>,
+        reason: ResultReason,
+        slash_bond: bool,
+    ) -> Result<()> {
+        ctx.accounts.resolve_dispute(code, winner, reason, slash_bond)?;
+        Ok(())
+    }
+
+    pub fn escalate_dispute(ctx: Context<EscalateDispute>, code: String) -> Result<()> {
+        ctx.accounts.escalate_dispute(code, &ctx.bumps)?;
+        Ok(())
+    }
+
+    pub fn cast_vote(ctx: Context<CastVote>, code: String, outcome: SideOutcome) -> Result<()> {
+        ctx.accounts.cast_vote(code, outcome)?;
+        Ok(())
+    }
+
+    pub fn expire_dispute_case(ctx: Context<ExpireDisputeCase>) -> Result<()> {
+        ctx.accounts.expire_dispute_case()?;
+        Ok(())
+    }
+
+    pub fn queue_withdrawal(ctx: Context<QueueWithdrawal>, amount: u64, destination: Pubkey) -> Result<()> {
+        ctx.accounts.queue_withdrawal(amount, destination, &ctx.bumps)?;
+        Ok(())
+    }
+
+    pub fn execute_withdrawal(ctx: Context<ExecuteWithdrawal>) -> Result<()>{
+        ctx.accounts.execute_withdrawal()?;
+        Ok(())
+    }
+
+    pub fn cancel_withdrawal(ctx: Context<CancelWithdrawal>) -> Result<()> {
+        ctx.accounts.cancel_withdrawal()?;
+        Ok(())
+    }
+
+    pub fn set_side_pool_params(ctx: Context<UpdateFees>, fee_bps: u16, cutoff: i64) -> Result<()> {
+        ctx.accounts.set_side_pool_params(fee_bps, cutoff)?;
+        Ok(())
+    }
+
+    pub fn set_time_control_bounds(
+        ctx: Context<UpdateConfig>,
+        min_base_seconds: u32,
+        max_base_seconds: u32,
+        max_increment_seconds: u32,
+        max_delay_seconds: u32,
+    ) -> Result<()> {
+        ctx.accounts.set_time_control_bounds(
+            min_base_seconds,
+            max_base_seconds,
+            max_increment_seconds,
+            max_delay_seconds,
+        )?;
+        Ok(())
+    }
+
+    pub fn set_bet_limits(ctx: Context<UpdateConfig>, min_bet: u64, max_bet: u64) -> Result<()> {
+        ctx.accounts.set_bet_limits(min_bet, max_bet)?;
+        Ok(())
+    }
+
+    pub fn set_category_params(ctx: Context<UpdateFees>, fee_bps: [u16; 4], timeouts: [i64; 4]) -> Result<()> {
+        ctx.accounts.set_category_params(fee_bps, timeouts)?;
+        Ok(())
+    }
+
+    pub fn set_reveal_window(ctx: Context<UpdateConfig>, reveal_window: i64) -> Result<()> {
+        ctx.accounts.set_reveal_window(reveal_window)?;
+        Ok(())
+    }
+
+    pub fn set_challenge_window(ctx: Context<UpdateConfig>, challenge_window: i64) -> Result<()> {
+        ctx.accounts.set_challenge_window(challenge_window)?;
+        Ok(())
+    }
+
+    pub fn set_dispute_params(ctx: Context<UpdateConfig>, dispute_window: i64, dispute_bond: u64) -> Result<()> {
+        ctx.accounts.set_dispute_params(dispute_window, dispute_bond)?;
+        Ok(())
+    }
+
+    pub fn create_fee_split(ctx: Context<CreateFeeSplit>, shares: Vec<FeeShare>, reserve: u64) -> Result<()> {
+        ctx.accounts.create_fee_split(shares, reserve, &ctx.bumps)?;
+        Ok(())
+    }
+
+    pub fn update_fee_split(ctx: Context<UpdateFeeSplit>, shares: Vec<FeeShare>, reserve: u64) -> Result<()> {
+        ctx.accounts.update_fee_split(shares, reserve)?;
+        Ok(())
+    }
+
+    pub fn distribute_fees<'info>(ctx: Context<'_, '_, 'info, 'info, DistributeFees<'info>>) -> Result<()> {
+        ctx.accounts.distribute_fees(ctx.remaining_accounts)?;
+        Ok(())
+    }
+
+    pub fn set_cancel_penalty_params(
+        ctx: Context<UpdateFees>,
+        cancel_penalty_bps: u16,
+        cancel_penalty_step: i64,
+        cancel_penalty_step_bps: u16,
+        max_cancel_penalty_bps: u16,
+        cancel_opponent_share_bps: u16,
+    ) -> Result<()> {
+        ctx.accounts.set_cancel_penalty_params(
+            cancel_penalty_bps,
+            cancel_penalty_step,
+            cancel_penalty_step_bps,
+            max_cancel_penalty_bps,
+            cancel_opponent_share_bps,
+        )?;
+        Ok(())
+    }
+
+    pub fn set_referral_fee_bps(ctx: Context<UpdateFees>, referral_fee_bps: u16) -> Result<()> {
+        ctx.accounts.set_referral_fee_bps(referral_fee_bps)?;
+        Ok(())
+    }
+
+    pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+        ctx.accounts.register_referrer(&ctx.bumps)?;
+        Ok(())
+    }
+
+    pub fn claim_referral_rewards(ctx: Context<ClaimReferralRewards>) -> Result<()> {
+        ctx.accounts.claim_referral_rewards()?;
+        Ok(())
+    }
+
+    pub fn grant_role(ctx: Context<GrantRole>, role: Role, holder: Pubkey) -> Result<()> {
+        ctx.accounts.grant_role(role, holder, &ctx.bumps)?;
+        Ok(())
+    }
+
+    pub fn revoke_role(ctx: Context<RevokeRole>) -> Result<()> {
+        ctx.accounts.revoke_role()?;
+        Ok(())
+    }
+
+    pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+        ctx.accounts.set_paused(paused)?;
+        Ok(())
+    }
+
+    pub fn create_arbiter_set(
+        ctx: Context<CreateArbiterSet>,
+        arbiters: Vec<Pubkey>,
+        threshold: u8,
+        arbiter_fee: u64,
+        vote_window: i64,
+    ) -> Result<()> {
+        ctx.accounts.create_arbiter_set(arbiters, threshold, arbiter_fee, vote_window, &ctx.bumps)?;
+        Ok(())
+    }
+
+    pub fn update_arbiter_set(
+        ctx: Context<UpdateArbiterSet>,
+        arbiters: Vec<Pubkey>,
+        threshold: u8,
+        arbiter_fee: u64,
+        vote_window: i64,
+    ) -> Result<()> {
+        ctx.accounts.update_arbiter_set(arbiters, threshold, arbiter_fee, vote_window)?;
+        Ok(())
+    }
+
+    pub fn set_oracle_bond_params(
+        ctx: Context<UpdateConfig>,
+        min_oracle_bond: u64,
+        oracle_unbond_cooldown: i64,
+        oracle_slash_bps: u16,
+    ) -> Result<()> {
+        ctx.accounts.set_oracle_bond_params(min_oracle_bond, oracle_unbond_cooldown, oracle_slash_bps)?;
+        Ok(())
+    }
+
+    pub fn set_instant_settlement(ctx: Context<UpdateConfig>, instant_settlement: bool) -> Result<()> {
+        ctx.accounts.set_instant_settlement(instant_settlement)?;
+        Ok(())
+    }
+
+    pub fn bond_oracle(ctx: Context<BondOracle>, amount: u64) -> Result<()> {
+        ctx.accounts.bond_oracle(amount, &ctx.bumps)?;
+        Ok(())
+    }
+
+    pub fn request_unbond(ctx: Context<RequestUnbond>) -> Result<()> {
+        ctx.accounts.request_unbond()?;
+        Ok(())
+    }
+
+    pub fn withdraw_oracle_bond(ctx: Context<WithdrawOracleBond>) -> Result<()> {
+        ctx.accounts.withdraw_oracle_bond()?;
+        Ok(())
+    }
+
+    pub fn slash_oracle(ctx: Context<SlashOracle>, amount: u64) -> Result<()> {
+        ctx.accounts.slash_oracle(amount)?;
+        Ok(())
+    }
+
+    pub fn set_admin_signers(ctx: Context<UpdateConfig>, signers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        ctx.accounts.set_admin_signers(signers, threshold)?;
+        Ok(())
+    }
+
+    pub fn create_proposal(ctx: Context<CreateProposal>, accounts: Vec<ProposalAccount>, data: Vec<u8>) -> Result<()> {
+        ctx.accounts.create_proposal(accounts, data, &ctx.bumps)?;
+        Ok(())
+    }
+
+    pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+        ctx.accounts.approve_proposal()?;
+        Ok(())
+    }
+
+    pub fn execute_proposal<'info>(ctx: Context<'_, '_, 'info, 'info, ExecuteProposal<'info>>) -> Result<()> {
+        ctx.accounts.execute_proposal(ctx.remaining_accounts)?;
+        Ok(())
+    }
+
+    pub fn cancel_proposal(ctx: Context<CancelProposal>) -> Result<()> {
+        ctx.accounts.cancel_proposal()?;
+        Ok(())
+    }
+
+    pub fn set_withdrawal_params(
+        ctx: Context<UpdateConfig>,
+        withdrawal_delay: i64,
+        withdrawal_epoch: i64,
+        withdrawal_epoch_cap: u64,
+    ) -> Result<()> {
+        ctx.accounts.set_withdrawal_params(withdrawal_delay, withdrawal_epoch, withdrawal_epoch_cap)?;
+        Ok(())
+    }
+
+    pub fn add_withdraw_destination(ctx: Context<UpdateConfig>, destination: Pubkey) -> Result<()> {
+        ctx.accounts.add_withdraw_destination(destination)?;
+        Ok(())
+    }
+
+    pub fn remove_withdraw_destination(ctx: Context<UpdateConfig>, destination: Pubkey) -> Result<()> {
+        ctx.accounts.remove_withdraw_destination(destination)?;
+        Ok(())
+    }
+
+    pub fn open_side_pool(ctx: Context<OpenSidePool>, code: String) -> Result<()> {
+        ctx.accounts.open_side_pool(code, &ctx.bumps)?;
+        Ok(())
+    }
+
+    pub fn place_side_bet(ctx: Context<PlaceSideBet>, outcome: SideOutcome, amount: u64) -> Result<()> {
+        ctx.accounts.place_side_bet(outcome, amount, &ctx.bumps)?;
+        Ok(())
+    }
+
+    pub fn claim_side_bet(ctx: Context<ClaimSideBet>) -> Result<()> {
+        ctx.accounts.claim_side_bet()?;
+        Ok(())
+    }
+
+    pub fn close_side_pool(ctx: Context<CloseSidePool>) -> Result<()> {
+        ctx.accounts.close_side_pool()?;
+        Ok(())
+    }
+
+    pub fn enqueue(
+        ctx: Context<Enqueue>,
+        bet_amount: u64,
+        time_control: TimeControl,
+        color_commitment: [u8; 32],
+    ) -> Result<()> {
+        ctx.accounts.enqueue(bet_amount, time_control, color_commitment, &ctx.bumps)?;
+        Ok(())
+    }
+
+    pub fn dequeue(ctx: Context<Dequeue>) -> Result<()> {
+        ctx.accounts.dequeue()?;
         Ok(())
     }
 
-    pub fn withdraw_from_treasury(ctx: Context<WithdrawTreasury>) -> Result<()>{
-        ctx.accounts.withdraw_from_treasury()?;
+    pub fn pair(ctx: Context<Pair>, seed: u64) -> Result<()> {
+        ctx.accounts.pair(seed, &ctx.bumps)?;
         Ok(())
     }
 }
@@ -0,0 +1,254 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+use anchor_lang::system_program::transfer;
+use anchor_lang::system_program::Transfer;
+
+use crate::events::MatchSettled;
+use crate::GameLog;
+use crate::MatchConfig;
+use crate::MatchState;
+use crate::ProtocolStats;
+use crate::ReferrerRewards;
+use crate::ResultReason;
+use crate::SideOutcome;
+use crate::SidePool;
+use crate::Status;
+use crate::Status::*;
+
+use crate::error::ErrorCode;
+
+/// Accounts every settlement path needs to pay out a match, borrowed from the instruction's own accounts.
+pub struct Payout<'a, 'info> {
+    pub player_a: AccountInfo<'info>, // either player can be the signer, depending on the settlement path
+    pub player_b: AccountInfo<'info>,
+    pub match_account: &'a mut Account<'info, MatchState>,
+    pub vault: &'a SystemAccount<'info>,
+    pub game_log: &'a Option<Account<'info, GameLog>>,
+    pub config: &'a Account<'info, MatchConfig>,
+    pub treasury_pda: &'a SystemAccount<'info>,
+    pub side_pool: &'a mut Option<Account<'info, SidePool>>,
+    pub side_vault: &'a Option<SystemAccount<'info>>,
+    pub stats: &'a mut Account<'info, ProtocolStats>,
+    pub referrer_rewards: [Option<&'a mut Account<'info, ReferrerRewards>>; 2], // players' referrers, required when the match has one
+    pub system_program: &'a Program<'info, System>,
+}
+
+impl<'a, 'info> Payout<'a, 'info> {
+    /// Pays the winner (or refunds both players on a draw) minus protocol fees and sweeps the rest to the treasury.
+    pub fn settle(&mut self, winner_key: Option<Pubkey>, reason: ResultReason) -> Result<()> {
+        require!(
+            self.match_account.status == InProgress || self.match_account.status == Disputed,
+            ErrorCode::InvalidMatchError
+        );
+        require_keys_eq!(
+            self.player_a.key(),
+            self.match_account.player_a,
+            ErrorCode::InvalidPlayerError
+        );
+        require_keys_eq!(
+            self.player_b.key(),
+            self.match_account.player_b.ok_or(ErrorCode::InvalidMatchError)?,
+            ErrorCode::InvalidPlayerError
+        );
+        // every settlement path pays rebates, so none can skip a referrer by leaving its account out
+        require!(
+            (self.match_account.referrer_a.is_none() || self.referrer_rewards[0].is_some())
+                && (self.match_account.referrer_b.is_none() || self.referrer_rewards[1].is_some()),
+            ErrorCode::InvalidReferralError
+        );
+
+        self.match_account.require_game_log_result(self.game_log, winner_key)?;
+
+        if winner_key.is_some() {
+            self.match_account.status = Completed;
+        } else {
+            self.match_account.status = Draw;
+        }
+        self.match_account.result_reason = Some(reason);
+
+        let req_balance = self.match_account.bet_amount.checked_mul(2);
+        require_eq!(
+            self.vault.lamports().to_string(),
+            req_balance.unwrap().to_string(),
+            ErrorCode::InvalidVaultBalanceError
+        );
+
+        let match_state = self.match_account.status;
+        let kept_fee: u64; // the protocol fee left in the vault once referrers are paid
+
+        match match_state {
+            Status::Completed => {
+                let winner = winner_key.unwrap();
+                self.match_account.winner = Some(winner);
+
+                let bet = self.match_account.bet_amount;
+                let total_bet_amount = bet.checked_mul(2).unwrap();
+                let fee_bps = self.win_fee_bps()?;
+                let winning_amount = total_bet_amount
+                    .checked_sub(total_bet_amount.checked_mul(fee_bps).unwrap().checked_div(10_000).unwrap())
+                    .unwrap();
+                let fee = total_bet_amount - winning_amount;
+
+                match (winner == self.player_a.key(), winner == self.player_b.key()) {
+                    (true, false) => self.vault_transfer(self.player_a.to_account_info(), winning_amount)?,
+                    (false, true) => self.vault_transfer(self.player_b.to_account_info(), winning_amount)?,
+                    (true, true) | (false, false) => {
+                        return err!(ErrorCode::InvalidWinnerError);
+                    }
+                }
+                let rebated = self.pay_referrers([fee / 2, fee / 2])?; // both stakes paid the fee
+                kept_fee = fee - rebated;
+                ProtocolStats::add(&mut self.stats.matches_completed, 1);
+                ProtocolStats::add(&mut self.stats.win_fees, kept_fee);
+            }
+            Status::Draw => {
+                let bet = self.match_account.bet_amount;
+                let draw_amount = bet
+                    .checked_sub(bet.checked_mul(100).unwrap().checked_div(10_000).unwrap())
+                    .unwrap(); // each person will get there money refunded by 1%
+
+                self.vault_transfer(self.player_a.to_account_info(), draw_amount)?;
+                self.vault_transfer(self.player_b.to_account_info(), draw_amount)?;
+                let rebated = self.pay_referrers([bet - draw_amount, bet - draw_amount])?;
+                kept_fee = (bet - draw_amount) * 2 - rebated;
+                ProtocolStats::add(&mut self.stats.matches_drawn, 1);
+                ProtocolStats::add(&mut self.stats.draw_fees, kept_fee);
+            }
+            Status::InProgress
+            | Status::Waiting
+            | Status::Disputed
+            | Status::Proposed
+            | Status::ChannelPending => {
+                return err!(ErrorCode::InvalidMatchError);
+            }
+        }
+
+        // Sweep any leftover lamports from the vault to the treasury via CPI
+        // It’s fine to transfer the full balance for a 0-space SystemAccount
+        let leftover = self.vault.lamports();
+        if leftover > 0 {
+            self.vault_transfer(self.treasury_pda.to_account_info(), leftover)?;
+        }
+        ProtocolStats::add(&mut self.stats.leftover_swept, leftover.saturating_sub(kept_fee));
+
+        if self.match_account.has_side_pool {
+            let outcome = match winner_key {
+                Some(key) if key == self.player_a.key() => SideOutcome::PlayerA,
+                Some(_) => SideOutcome::PlayerB,
+                None => SideOutcome::Draw,
+            };
+            self.resolve_side_pool(outcome)?;
+        }
+
+        emit!(MatchSettled {
+            match_account: self.match_account.key(),
+            winner: winner_key,
+            status: self.match_account.status,
+            reason,
+            bet_amount: self.match_account.bet_amount,
+        });
+
+        Ok(())
+    }
+
+    fn win_fee_bps(&self) -> Result<u64> {
+        let bet = self.match_account.bet_amount;
+        let tier_bps: u64;
+
+        if bet <= LAMPORTS_PER_SOL {
+            tier_bps = 50; // 0.5% of 2* bet_ammount
+        } else if LAMPORTS_PER_SOL < bet && bet <= LAMPORTS_PER_SOL * 5 {
+            tier_bps = 100; // 1% of 2* bet_ammount
+        } else if bet > LAMPORTS_PER_SOL * 5 {
+            tier_bps = 150; // 1.5% of 2* bet_ammount
+        } else {
+            return err!(ErrorCode::InvalidBetAmount);
+        }
+
+        // faster time controls can be priced differently on top of the bet size tier
+        let category = self.match_account.time_control.category();
+        Ok(tier_bps
+            .checked_add(self.config.category_fee_bps[category as usize] as u64)
+            .ok_or(ErrorCode::MathOverflow)?
+            .min(10_000))
+    }
+
+    /// Pays each referred player's referrer its share of the fee that player paid and returns the total,
+    /// the rest goes to the treasury.
+    fn pay_referrers(&mut self, fees: [u64; 2]) -> Result<u64> {
+        let mut rebated = 0;
+        let referrers = [self.match_account.referrer_a, self.match_account.referrer_b];
+        for (i, referrer) in referrers.into_iter().enumerate() {
+            let (Some(referrer), Some(rewards)) = (referrer, self.referrer_rewards[i].as_ref()) else {
+                continue;
+            };
+            require_keys_eq!(rewards.referrer, referrer, ErrorCode::InvalidReferralError);
+
+            let share = (fees[i] as u128 * self.config.referral_fee_bps as u128 / 10_000) as u64;
+            if share == 0 {
+                continue;
+            }
+            self.vault_transfer(rewards.to_account_info(), share)?;
+            if let Some(rewards) = self.referrer_rewards[i].as_mut() {
+                rewards.credit(share)?;
+            }
+            rebated += share;
+        }
+        ProtocolStats::add(&mut self.stats.referral_rebates, rebated);
+
+        Ok(rebated)
+    }
+
+    fn vault_transfer(&self, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+        let transfer_accounts = Transfer {
+            from: self.vault.to_account_info(),
+            to,
+        };
+
+        let signer_seeds: &[&[&[u8]]; 1] = &[&[
+            b"vault",
+            self.match_account.to_account_info().key.as_ref(), //temporary value dropped while borrowed, that's why removed () after key
+            &[self.match_account.vault_bump],
+        ]];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.system_program.to_account_info(),
+            transfer_accounts,
+            signer_seeds,
+        );
+
+        transfer(cpi_ctx, amount)
+    }
+
+    fn resolve_side_pool(&mut self, outcome: SideOutcome) -> Result<()> {
+        let (Some(side_pool), Some(side_vault)) = (self.side_pool.as_mut(), self.side_vault.as_ref()) else {
+            return err!(ErrorCode::InvalidSidePool);
+        };
+
+        let fee = side_pool.resolve(outcome, self.config.side_pool_fee_bps)?;
+        ProtocolStats::add(&mut self.stats.side_pool_fees, fee);
+        if fee > 0 {
+            let transfer_accounts = Transfer {
+                from: side_vault.to_account_info(),
+                to: self.treasury_pda.to_account_info(),
+            };
+
+            let side_pool_key = side_pool.key();
+            let signer_seeds: &[&[&[u8]]; 1] = &[&[
+                b"side_vault",
+                side_pool_key.as_ref(),
+                &[side_pool.vault_bump],
+            ]];
+
+            let cpi_ctx = CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                transfer_accounts,
+                signer_seeds,
+            );
+
+            transfer(cpi_ctx, fee)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::SideOutcome;
+
+/// Largest panel an arbiter set can hold.
+pub const MAX_ARBITERS: usize = 9;
+
+/// Panel that decides escalated disputes, registered in `MatchConfig::arbiter_set`.
+#[account]
+#[derive(InitSpace)]
+pub struct ArbiterSet {
+    #[max_len(MAX_ARBITERS)]
+    pub arbiters: Vec<Pubkey>,
+    pub threshold: u8, // matching votes needed to decide a case
+    pub arbiter_fee: u64, // lamports paid out of the dispute bond for each vote cast
+    pub vote_window: i64, // seconds a case stays open before the admin can take it back
+    pub bump: u8,
+}
+
+/// One escalated dispute, the panel is copied in so later set changes don't affect open cases.
+#[account]
+#[derive(InitSpace)]
+pub struct DisputeCase {
+    pub proposal: Pubkey,
+    pub payer: Pubkey,
+    #[max_len(MAX_ARBITERS)]
+    pub arbiters: Vec<Pubkey>,
+    pub threshold: u8,
+    pub arbiter_fee: u64,
+    #[max_len(MAX_ARBITERS)]
+    pub votes: Vec<ArbiterVote>,
+    pub deadline: i64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct ArbiterVote {
+    pub arbiter: Pubkey,
+    pub outcome: SideOutcome,
+}
+
+impl ArbiterSet {
+    pub fn configure(
+        &mut self,
+        arbiters: Vec<Pubkey>,
+        threshold: u8,
+        arbiter_fee: u64,
+        vote_window: i64,
+    ) -> Result<()> {
+        require!(
+            !arbiters.is_empty() && arbiters.len() <= MAX_ARBITERS,
+            ErrorCode::InvalidArbiterSetError
+        );
+        require!(
+            arbiters.iter().enumerate().all(|(i, a)| !arbiters[..i].contains(a)),
+            ErrorCode::InvalidArbiterSetError
+        );
+        // a strict majority, so two outcomes can never both reach it
+        require!(
+            (threshold as usize) * 2 > arbiters.len() && (threshold as usize) <= arbiters.len(),
+            ErrorCode::InvalidArbiterSetError
+        );
+        require!(vote_window > 0, ErrorCode::InvalidConfigError);
+
+        self.arbiters = arbiters;
+        self.threshold = threshold;
+        self.arbiter_fee = arbiter_fee;
+        self.vote_window = vote_window;
+
+        Ok(())
+    }
+}
+
+impl DisputeCase {
+    /// Records the vote and returns how many assigned arbiters now back `outcome`.
+    pub fn vote(&mut self, arbiter: Pubkey, outcome: SideOutcome) -> Result<usize> {
+        require!(
+            self.arbiters.contains(&arbiter),
+            ErrorCode::NotAnArbiterError
+        );
+        require!(
+            !self.votes.iter().any(|vote| vote.arbiter == arbiter),
+            ErrorCode::AlreadyVotedError
+        );
+
+        self.votes.push(ArbiterVote { arbiter, outcome });
+        Ok(self.votes.iter().filter(|vote| vote.outcome == outcome).count())
+    }
+}
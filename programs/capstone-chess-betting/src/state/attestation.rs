@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+use crate::ResultReason;
+
+/// Result an oracle signs off chain, anyone can then relay it with `settle_with_attestation`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct Attestation {
+    pub match_account: Pubkey,
+    pub winner: Option<Pubkey>, // None for a draw
+    pub reason: ResultReason,
+    pub nonce: u64, // the match's settlement_nonce
+}
+
+impl Attestation {
+    pub fn message(&self) -> Result<Vec<u8>> {
+        let mut message = Vec::with_capacity(32 + 33 + 1 + 8);
+        self.serialize(&mut message)?;
+        Ok(message)
+    }
+}
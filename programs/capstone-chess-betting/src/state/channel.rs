@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+use crate::ed25519::verify_signature;
+use crate::MatchState;
+use crate::ResultReason;
+use crate::SideOutcome;
+
+use crate::error::ErrorCode;
+
+/// Latest game state both players signed off chain, replaceable by a newer one until `challenge_deadline`.
+#[account]
+#[derive(InitSpace)]
+pub struct ChannelClaim {
+    pub match_account: Pubkey,
+    pub submitter: Pubkey, // paid the rent, gets it back on settlement
+    pub nonce: u64,
+    pub board_hash: [u8; 32],
+    pub result: Option<SideOutcome>,
+    pub reason: ResultReason,
+    pub challenge_deadline: i64,
+    pub bump: u8,
+}
+
+/// Message both players sign for every off-chain update, the nonce goes up by at least one each time.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct ChannelState {
+    pub match_account: Pubkey,
+    pub nonce: u64,
+    pub board_hash: [u8; 32], // opaque to the program, lets either player prove which position was signed
+    pub result: Option<SideOutcome>, // None while the game is still being played
+    pub reason: ResultReason, // ignored until there is a result
+}
+
+impl ChannelState {
+    pub fn message(&self) -> Result<Vec<u8>> {
+        let mut message = Vec::with_capacity(32 + 8 + 32 + 2 + 1);
+        self.serialize(&mut message)?;
+        Ok(message)
+    }
+
+    /// Requires the state to be for `match_key` and signed by both of its players in this transaction.
+    pub fn verify(&self, instructions: &AccountInfo, match_key: Pubkey, match_state: &MatchState) -> Result<()> {
+        require_keys_eq!(self.match_account, match_key, ErrorCode::InvalidChannelStateError);
+        let player_b = match_state.player_b.ok_or(ErrorCode::InvalidMatchError)?;
+
+        let message = self.message()?;
+        verify_signature(instructions, &match_state.player_a, &message)?;
+        verify_signature(instructions, &player_b, &message)
+    }
+}
+
+impl ChannelClaim {
+    pub fn store(&mut self, state: &ChannelState) {
+        self.nonce = state.nonce;
+        self.board_hash = state.board_hash;
+        self.result = state.result;
+        self.reason = state.reason;
+    }
+}
@@ -1,9 +1,249 @@
 use anchor_lang::prelude::*;
 
+use crate::error::ErrorCode;
+use crate::{Role, RoleAssignment};
+use crate::{
+    DEFAULT_CANCEL_OPPONENT_SHARE_BPS, DEFAULT_CANCEL_PENALTY_BPS, DEFAULT_CANCEL_PENALTY_STEP,
+    DEFAULT_CANCEL_PENALTY_STEP_BPS, DEFAULT_CATEGORY_TIMEOUTS, DEFAULT_CHALLENGE_WINDOW,
+    DEFAULT_DISPUTE_BOND, DEFAULT_DISPUTE_WINDOW, DEFAULT_MAX_BASE_SECONDS, DEFAULT_MAX_BET,
+    DEFAULT_MAX_CANCEL_PENALTY_BPS, DEFAULT_MAX_DELAY_SECONDS, DEFAULT_MAX_INCREMENT_SECONDS,
+    DEFAULT_MIN_BASE_SECONDS, DEFAULT_MIN_BET, DEFAULT_MIN_ORACLE_BOND, DEFAULT_ORACLE_SLASH_BPS,
+    DEFAULT_ORACLE_UNBOND_COOLDOWN, DEFAULT_REFERRAL_FEE_BPS, DEFAULT_REVEAL_WINDOW,
+    DEFAULT_SIDE_BET_CUTOFF, DEFAULT_SIDE_POOL_FEE_BPS, DEFAULT_WITHDRAWAL_DELAY,
+    DEFAULT_WITHDRAWAL_EPOCH, DEFAULT_WITHDRAWAL_EPOCH_CAP,
+};
+
+/// Most treasury withdrawal destinations the config can allow.
+pub const MAX_WITHDRAW_DESTINATIONS: usize = 8;
+
+/// Largest admin signer set the config can hold.
+pub const MAX_ADMIN_SIGNERS: usize = 10;
+
 #[account]
 #[derive(InitSpace)]
 pub struct MatchConfig {
-    pub authority: Pubkey,
+    pub authority: Pubkey, // the admin PDA once a signer set is configured, see set_admin_signers
     pub treasury_bump: u8,
-    pub config_bump: u8
-}
\ No newline at end of file
+    pub config_bump: u8,
+    pub side_pool_fee_bps: u16,
+    pub side_bet_cutoff: i64, // seconds after accept_match during which spectators can bet
+    pub min_base_seconds: u32,
+    pub max_base_seconds: u32,
+    pub max_increment_seconds: u32,
+    pub max_delay_seconds: u32,
+    pub min_bet: u64, // lamports, SOL only until token matches exist
+    pub max_bet: u64,
+    pub category_fee_bps: [u16; 4], // added to the win fee tier, indexed by TimeCategory
+    pub category_timeouts: [i64; 4], // grace after the nominal game length, indexed by TimeCategory
+    pub reveal_window: i64, // seconds player A has after accept_match to reveal the colour secret
+    pub challenge_window: i64, // seconds a submitted channel state can be replaced by a newer one
+    pub dispute_window: i64, // seconds between propose_result and finalize_result
+    pub dispute_bond: u64, // lamports a player locks to dispute a proposed result
+    pub arbiter_set: Option<Pubkey>, // panel escalated disputes go to, see create_arbiter_set
+    pub min_oracle_bond: u64, // lamports an oracle must have bonded to settle
+    pub oracle_unbond_cooldown: i64, // seconds between request_unbond and withdraw_oracle_bond
+    pub oracle_slash_bps: u16, // share of the bond paid to the disputer when an oracle's result is overturned
+    #[max_len(MAX_WITHDRAW_DESTINATIONS)]
+    pub withdraw_destinations: Vec<WithdrawDestination>, // the only accounts treasury withdrawals can go to
+    pub total_withdrawn: u64, // lamports withdrawn from the treasury over its lifetime
+    pub withdrawal_count: u64, // id of the next queued withdrawal
+    pub withdrawal_delay: i64, // seconds between queue_withdrawal and execute_withdrawal
+    pub withdrawal_epoch: i64, // length in seconds of a withdrawal cap period
+    pub withdrawal_epoch_cap: u64, // lamports that can leave the treasury per period
+    pub pending_withdrawal_limits: Option<PendingWithdrawalLimits>, // looser limits waiting out the withdrawal delay
+    pub current_epoch: i64, // index of the period withdrawn_this_epoch counts
+    pub withdrawn_this_epoch: u64,
+    #[max_len(MAX_ADMIN_SIGNERS)]
+    pub admin_signers: Vec<Pubkey>, // approve proposals run as the admin PDA
+    pub admin_threshold: u8, // approvals a proposal needs before it can be executed
+    pub admin_bump: u8,
+    pub proposal_count: u64, // id of the next admin proposal
+    pub cancel_penalty_bps: u16, // share of the canceller's stake kept when an accepted match is cancelled
+    pub cancel_penalty_step: i64, // seconds of play after which the penalty grows by cancel_penalty_step_bps
+    pub cancel_penalty_step_bps: u16,
+    pub max_cancel_penalty_bps: u16,
+    pub cancel_opponent_share_bps: u16, // share of the penalty paid to the opponent, the rest goes to the treasury
+    pub referral_fee_bps: u16, // share of a referred player's fee rebated to their referrer
+    pub paused: bool, // no new matches, queue entries or side pools while set
+    pub instant_settlement: bool, // lets oracles call final_payouts on matches with no game log or reports
+}
+
+/// An allowlisted withdrawal destination, usable once `active_at` has passed.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct WithdrawDestination {
+    pub key: Pubkey,
+    pub active_at: i64,
+}
+
+/// Epoch length and cap that would let more leave the treasury, applied once `active_at` has passed.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct PendingWithdrawalLimits {
+    pub epoch: i64,
+    pub epoch_cap: u64,
+    pub active_at: i64,
+}
+
+impl MatchConfig {
+    /// Every setting at its default from constants.rs, as init_config creates it.
+    pub fn new(authority: Pubkey, treasury_bump: u8, config_bump: u8) -> Self {
+        MatchConfig {
+            authority,
+            treasury_bump,
+            config_bump,
+            side_pool_fee_bps: DEFAULT_SIDE_POOL_FEE_BPS,
+            side_bet_cutoff: DEFAULT_SIDE_BET_CUTOFF,
+            min_base_seconds: DEFAULT_MIN_BASE_SECONDS,
+            max_base_seconds: DEFAULT_MAX_BASE_SECONDS,
+            max_increment_seconds: DEFAULT_MAX_INCREMENT_SECONDS,
+            max_delay_seconds: DEFAULT_MAX_DELAY_SECONDS,
+            min_bet: DEFAULT_MIN_BET,
+            max_bet: DEFAULT_MAX_BET,
+            category_fee_bps: [0; 4],
+            category_timeouts: DEFAULT_CATEGORY_TIMEOUTS,
+            reveal_window: DEFAULT_REVEAL_WINDOW,
+            challenge_window: DEFAULT_CHALLENGE_WINDOW,
+            dispute_window: DEFAULT_DISPUTE_WINDOW,
+            dispute_bond: DEFAULT_DISPUTE_BOND,
+            arbiter_set: None,
+            min_oracle_bond: DEFAULT_MIN_ORACLE_BOND,
+            oracle_unbond_cooldown: DEFAULT_ORACLE_UNBOND_COOLDOWN,
+            oracle_slash_bps: DEFAULT_ORACLE_SLASH_BPS,
+            withdraw_destinations: Vec::new(),
+            total_withdrawn: 0,
+            withdrawal_count: 0,
+            withdrawal_delay: DEFAULT_WITHDRAWAL_DELAY,
+            withdrawal_epoch: DEFAULT_WITHDRAWAL_EPOCH,
+            withdrawal_epoch_cap: DEFAULT_WITHDRAWAL_EPOCH_CAP,
+            pending_withdrawal_limits: None,
+            current_epoch: 0,
+            withdrawn_this_epoch: 0,
+            admin_signers: Vec::new(),
+            admin_threshold: 0,
+            admin_bump: 0,
+            proposal_count: 0,
+            cancel_penalty_bps: DEFAULT_CANCEL_PENALTY_BPS,
+            cancel_penalty_step: DEFAULT_CANCEL_PENALTY_STEP,
+            cancel_penalty_step_bps: DEFAULT_CANCEL_PENALTY_STEP_BPS,
+            max_cancel_penalty_bps: DEFAULT_MAX_CANCEL_PENALTY_BPS,
+            cancel_opponent_share_bps: DEFAULT_CANCEL_OPPONENT_SHARE_BPS,
+            referral_fee_bps: DEFAULT_REFERRAL_FEE_BPS,
+            paused: false,
+            instant_settlement: false,
+        }
+    }
+
+    /// Allowlisted and past its activation delay.
+    pub fn is_withdraw_destination(&self, key: &Pubkey, now: i64) -> bool {
+        self.withdraw_destinations
+            .iter()
+            .any(|destination| destination.key == *key && destination.active_at <= now)
+    }
+
+    /// Tighter limits apply at once and drop any looser ones still waiting,
+    /// looser ones wait out the withdrawal delay so a compromised admin can't drain the treasury in one go.
+    pub fn set_withdrawal_limits(&mut self, epoch: i64, epoch_cap: u64, now: i64) -> Result<()> {
+        if epoch >= self.withdrawal_epoch && epoch_cap <= self.withdrawal_epoch_cap {
+            self.pending_withdrawal_limits = None;
+            self.apply_withdrawal_limits(epoch, epoch_cap, now);
+        } else {
+            self.pending_withdrawal_limits = Some(PendingWithdrawalLimits {
+                epoch,
+                epoch_cap,
+                active_at: now
+                    .checked_add(self.withdrawal_delay)
+                    .ok_or(ErrorCode::MathOverflow)?,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn apply_withdrawal_limits(&mut self, epoch: i64, epoch_cap: u64, now: i64) {
+        // keep what was already withdrawn counting against the new period
+        self.withdrawal_epoch = epoch;
+        self.withdrawal_epoch_cap = epoch_cap;
+        self.current_epoch = now / epoch;
+    }
+
+    /// Applies looser limits whose delay has passed and starts the period `now` falls in if needed.
+    fn roll_epoch(&mut self, now: i64) {
+        if let Some(pending) = self.pending_withdrawal_limits {
+            if now >= pending.active_at {
+                self.pending_withdrawal_limits = None;
+                self.apply_withdrawal_limits(pending.epoch, pending.epoch_cap, now);
+            }
+        }
+
+        let epoch = now / self.withdrawal_epoch;
+        if epoch != self.current_epoch {
+            self.current_epoch = epoch;
+            self.withdrawn_this_epoch = 0;
+        }
+    }
+
+    /// Lamports that can still leave the treasury in the period `now` falls in.
+    pub fn epoch_allowance(&mut self, now: i64) -> u64 {
+        self.roll_epoch(now);
+        self.withdrawal_epoch_cap.saturating_sub(self.withdrawn_this_epoch)
+    }
+
+    /// Counts `amount` against the cap of the period `now` falls in, starting a new period if needed.
+    pub fn record_epoch_withdrawal(&mut self, amount: u64, now: i64) -> Result<()> {
+        self.roll_epoch(now);
+
+        let withdrawn = self
+            .withdrawn_this_epoch
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(
+            withdrawn <= self.withdrawal_epoch_cap,
+            ErrorCode::WithdrawalCapError
+        );
+        self.withdrawn_this_epoch = withdrawn;
+
+        Ok(())
+    }
+
+    pub fn validate_bet(&self, bet_amount: u64) -> Result<()> {
+        require!(
+            bet_amount >= self.min_bet && bet_amount <= self.max_bet,
+            ErrorCode::BetOutOfRangeError
+        );
+        Ok(())
+    }
+
+    /// Penalty for cancelling an accepted match `elapsed` seconds after acceptance,
+    /// split into the opponent's compensation and the protocol's share.
+    /// Measured from `accepted_at` rather than `created_at`, time spent waiting for an opponent
+    /// isn't game time and shouldn't make leaving more expensive.
+    pub fn cancel_penalty(&self, bet_amount: u64, elapsed: i64) -> (u64, u64) {
+        let steps = (elapsed.max(0) / self.cancel_penalty_step) as u64;
+        let bps = (self.cancel_penalty_bps as u64)
+            .saturating_add(steps.saturating_mul(self.cancel_penalty_step_bps as u64))
+            .min(self.max_cancel_penalty_bps as u64);
+
+        let penalty = (bet_amount as u128 * bps as u128 / 10_000) as u64;
+        let compensation = (penalty as u128 * self.cancel_opponent_share_bps as u128 / 10_000) as u64;
+        (compensation, penalty - compensation)
+    }
+
+    /// The authority holds every role, anyone else needs a matching assignment.
+    /// Admin, Treasurer and FeeManager assignments stop counting once a signer set is configured,
+    /// so config, fee and treasury changes need a proposal. Pausers and oracles keep acting alone.
+    pub fn has_role(
+        &self,
+        role: Role,
+        key: &Pubkey,
+        assignment: &Option<Account<RoleAssignment>>,
+    ) -> bool {
+        if *key == self.authority {
+            return true;
+        }
+        if self.admin_threshold > 0 && role.needs_signer_set() {
+            return false;
+        }
+        assignment
+            .as_ref()
+            .is_some_and(|assignment| assignment.grants(role, key))
+    }
+}
@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+
+/// Most beneficiaries a fee split can pay.
+pub const MAX_BENEFICIARIES: usize = 8;
+
+/// How distribute_fees shares out the treasury, shares are in basis points and add up to 100%.
+#[account]
+#[derive(InitSpace)]
+pub struct FeeSplit {
+    #[max_len(MAX_BENEFICIARIES)]
+    pub beneficiaries: Vec<Beneficiary>,
+    pub reserve: u64, // lamports always left in the treasury above rent
+    pub total_distributed: u64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct Beneficiary {
+    pub key: Pubkey,
+    pub share_bps: u16,
+    pub total_received: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct FeeShare {
+    pub beneficiary: Pubkey,
+    pub share_bps: u16,
+}
+
+impl Beneficiary {
+    /// Rounds down, the dust stays in the treasury for the next distribution.
+    pub fn share_of(&self, amount: u64) -> u64 {
+        (amount as u128 * self.share_bps as u128 / 10_000) as u64
+    }
+}
+
+impl FeeSplit {
+    /// Replaces the shares, beneficiaries that stay in the split keep their running totals.
+    pub fn configure(&mut self, shares: Vec<FeeShare>, reserve: u64) -> Result<()> {
+        require!(
+            !shares.is_empty() && shares.len() <= MAX_BENEFICIARIES,
+            ErrorCode::InvalidFeeSplitError
+        );
+        require!(
+            shares
+                .iter()
+                .enumerate()
+                .all(|(i, s)| !shares[..i].iter().any(|o| o.beneficiary == s.beneficiary)),
+            ErrorCode::InvalidFeeSplitError
+        );
+        require!(
+            shares.iter().map(|s| s.share_bps as u32).sum::<u32>() == 10_000,
+            ErrorCode::InvalidFeeBps
+        );
+
+        self.beneficiaries = shares
+            .iter()
+            .map(|share| Beneficiary {
+                key: share.beneficiary,
+                share_bps: share.share_bps,
+                total_received: self
+                    .beneficiaries
+                    .iter()
+                    .find(|b| b.key == share.beneficiary)
+                    .map_or(0, |b| b.total_received),
+            })
+            .collect();
+        self.reserve = reserve;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+use crate::chess::{Board, Color, CompactBoard, GameStatus, Move};
+use crate::TimeControl;
+
+/// Longest game a log can hold, in plies.
+pub const MAX_PLIES: usize = 512;
+
+/// Positions since the last capture or pawn move, the only ones that can repeat.
+pub const MAX_REPETITION_WINDOW: usize = 101;
+
+#[account]
+#[derive(InitSpace)]
+pub struct GameLog {
+    pub match_account: Pubkey,
+    pub payer: Pubkey,
+    pub board: CompactBoard,
+    pub started_at: i64,
+    pub last_move_at: i64,
+    pub white_remaining: i64, // seconds left on each clock as of last_move_at
+    pub black_remaining: i64,
+    pub hash_chain: [u8; 32], // sha256 over the previous link, the packed move and its timestamp
+    #[max_len(MAX_PLIES)]
+    pub moves: Vec<u16>, // packed with chess::Move::pack
+    #[max_len(MAX_PLIES)]
+    pub move_times: Vec<u32>, // seconds since started_at
+    #[max_len(MAX_REPETITION_WINDOW)]
+    pub position_hashes: Vec<u64>,
+    pub status: BoardStatus,
+    pub winner: Option<Pubkey>,
+    pub bump: u8,
+}
+
+/// On-board result of the logged game, `Ongoing` until the rules end it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BoardStatus {
+    Ongoing,
+    Checkmate,
+    Stalemate,
+    FiftyMoveRule,
+    ThreefoldRepetition,
+    InsufficientMaterial,
+    Timeout,
+}
+
+impl Space for BoardStatus {
+    const INIT_SPACE: usize = 1;
+}
+
+impl From<GameStatus> for BoardStatus {
+    fn from(status: GameStatus) -> Self {
+        match status {
+            GameStatus::Ongoing => BoardStatus::Ongoing,
+            GameStatus::Checkmate { .. } => BoardStatus::Checkmate,
+            GameStatus::Stalemate => BoardStatus::Stalemate,
+            GameStatus::FiftyMoveRule => BoardStatus::FiftyMoveRule,
+            GameStatus::ThreefoldRepetition => BoardStatus::ThreefoldRepetition,
+            GameStatus::InsufficientMaterial => BoardStatus::InsufficientMaterial,
+        }
+    }
+}
+
+impl GameLog {
+    pub fn genesis_hash(match_account: &Pubkey, board: &Board) -> [u8; 32] {
+        hashv(&[match_account.as_ref(), &board.pack().squares]).to_bytes()
+    }
+
+    /// Appends an already validated move, `board` being the position after it.
+    pub fn record(&mut self, mv: Move, board: &Board, now: i64) {
+        let packed = mv.pack();
+        let elapsed = now.saturating_sub(self.started_at).clamp(0, u32::MAX as i64) as u32;
+
+        self.hash_chain = hashv(&[&self.hash_chain, &packed.to_le_bytes(), &now.to_le_bytes()]).to_bytes();
+        self.moves.push(packed);
+        self.move_times.push(elapsed);
+        self.last_move_at = now;
+
+        // a capture or pawn move makes every earlier position unreachable
+        if board.halfmove_clock == 0 {
+            self.position_hashes.clear();
+        }
+        if self.position_hashes.len() == MAX_REPETITION_WINDOW {
+            self.position_hashes.remove(0);
+        }
+        self.position_hashes.push(board.hash());
+        self.board = board.pack();
+    }
+
+    /// Seconds the side to move has used since the last move, after the delay is spent.
+    pub fn clock_charge(&self, time_control: &TimeControl, now: i64) -> i64 {
+        let thinking = now.saturating_sub(self.last_move_at).max(0);
+        thinking.saturating_sub(time_control.delay_seconds as i64).max(0)
+    }
+
+    pub fn remaining(&self, color: Color) -> i64 {
+        match color {
+            Color::White => self.white_remaining,
+            Color::Black => self.black_remaining,
+        }
+    }
+
+    pub fn has_flagged(&self, color: Color, time_control: &TimeControl, now: i64) -> bool {
+        self.clock_charge(time_control, now) >= self.remaining(color)
+    }
+
+    /// Stops the mover's clock at `now` and adds the increment, called before `record`.
+    pub fn charge_clock(&mut self, color: Color, time_control: &TimeControl, now: i64) {
+        let left = self.remaining(color) - self.clock_charge(time_control, now)
+            + time_control.increment_seconds as i64;
+        match color {
+            Color::White => self.white_remaining = left,
+            Color::Black => self.black_remaining = left,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.status != BoardStatus::Ongoing
+    }
+}
@@ -1,16 +1,43 @@
 use anchor_lang::prelude::*;
 
+use crate::error::ErrorCode;
+use crate::GameLog;
+use crate::MatchConfig;
+use crate::ResultReason;
+use crate::ResultReport;
+use crate::SideOutcome;
+use crate::TimeControl;
+use crate::Variant;
+
 #[account]
 #[derive(InitSpace)]
 pub struct MatchState{
     pub seed: u64, 
     pub bet_amount: u64, 
-    pub match_duration: u32, 
+    pub time_control: TimeControl, 
+    pub variant: Variant, 
+    pub start_position: Option<u16>, // Chess960 position, drawn with the colours in reveal_colors
+    pub color_commitment: [u8; 32], // sha256 of player A's secret
+    pub player_b_commitment: Option<[u8; 32]>, // paired matches only, cleared once player B reveals into player_b_random
+    pub player_b_random: [u8; 32], 
+    pub white: Option<Pubkey>, 
     pub player_a: Pubkey, 
     pub player_b: Option<Pubkey>, 
+    pub referrer_a: Option<Pubkey>, // copied from each player's Referral, gets a share of their fees
+    pub referrer_b: Option<Pubkey>,
+    pub payer: Pubkey, // funded the record's rent, gets it back when the match is closed
     pub created_at: i64, 
+    pub accepted_at: i64, 
+    pub reveal_window_start: i64, // acceptance, or player B's reveal on a paired match
     pub winner: Option<Pubkey>, 
+    pub result_reason: Option<ResultReason>, // set on settlement
     pub status: Status, 
+    pub has_side_pool: bool, 
+    pub has_game_log: bool, 
+    pub reported_a: Option<ResultReport>, // each player's claimed result, see report_result
+    pub reported_b: Option<ResultReport>,
+    pub settlement_nonce: u64, // slot the record was created in, signed by oracles so attestations can't outlive it
+    pub channel_nonce: u64, // nonce of the last expired channel claim, older states can't be submitted again
     pub bump: u8, 
     pub vault_bump: u8 
 }
@@ -20,9 +47,85 @@ pub enum Status {
     Waiting,
     InProgress,
     Completed,
-    Draw
+    Draw,
+    Disputed, // the players reported different results, waiting on the oracle or admin
+    Proposed, // a result is waiting out its challenge window, see ResultProposal
+    ChannelPending // a signed channel state is waiting out its challenge window, see ChannelClaim
 }
 
 impl Space for Status {
     const INIT_SPACE: usize = 1; // size in bytes
-}
\ No newline at end of file
+}
+
+impl MatchState {
+    /// Past this point an accepted match has overrun both clocks plus the category grace period.
+    pub fn timeout_deadline(&self, config: &MatchConfig) -> Result<i64> {
+        let game_seconds = self
+            .time_control
+            .estimated_seconds()
+            .checked_mul(2)
+            .ok_or(ErrorCode::MathOverflow)? as i64;
+
+        self.accepted_at
+            .checked_add(game_seconds)
+            .and_then(|x| x.checked_add(config.category_timeouts[self.time_control.category() as usize]))
+            .ok_or(error!(ErrorCode::MathOverflow))
+    }
+
+    /// Whoever isn't white, once colours are assigned.
+    pub fn black(&self) -> Option<Pubkey> {
+        let white = self.white?;
+        if white == self.player_a {
+            self.player_b
+        } else {
+            Some(self.player_a)
+        }
+    }
+
+    pub fn winner_for(&self, outcome: SideOutcome) -> Option<Pubkey> {
+        match outcome {
+            SideOutcome::PlayerA => Some(self.player_a),
+            SideOutcome::PlayerB => self.player_b,
+            SideOutcome::Draw => None,
+        }
+    }
+
+    /// Oracles settle through final_payouts only while the admin allows it and only on matches that carry no
+    /// evidence of their own, anything with a game log or a player's report goes through propose_result.
+    /// Signed attestations don't need this, the signature over the settlement nonce is the authorization.
+    pub fn require_instant_settlement(&self, config: &MatchConfig, settler: &Pubkey) -> Result<()> {
+        if *settler == config.authority {
+            return Ok(());
+        }
+        require!(
+            config.instant_settlement
+                && self.status == Status::InProgress
+                && !self.has_game_log
+                && self.reported_a.is_none()
+                && self.reported_b.is_none(),
+            ErrorCode::InstantSettlementError
+        );
+
+        Ok(())
+    }
+
+    /// A game played through submit_move is its own evidence, no result can contradict a finished board.
+    pub fn require_game_log_result(&self, game_log: &Option<Account<GameLog>>, winner: Option<Pubkey>) -> Result<()> {
+        if self.has_game_log {
+            let game_log = game_log.as_ref().ok_or(ErrorCode::InvalidGameLogError)?;
+            require!(
+                !game_log.is_finished() || game_log.winner == winner,
+                ErrorCode::GameLogResultMismatchError
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Deadline for whichever reveal is pending, player B's on a paired match and player A's otherwise.
+    pub fn reveal_deadline(&self, config: &MatchConfig) -> Result<i64> {
+        self.reveal_window_start
+            .checked_add(config.reveal_window)
+            .ok_or(error!(ErrorCode::MathOverflow))
+    }
+}
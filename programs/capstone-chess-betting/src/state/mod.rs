@@ -1,5 +1,39 @@
 pub mod match_state;
 pub mod config;
+pub mod side_pool;
+pub mod queue_ticket;
+pub mod time_control;
+pub mod variant;
+pub mod game_log;
+pub mod channel;
+pub mod result_reason;
+pub mod attestation;
+pub mod result_proposal;
+pub mod arbiter;
+pub mod oracle_bond;
+pub mod pending_withdrawal;
+pub mod proposal;
+pub mod role;
+pub mod fee_split;
+pub mod referral;
+pub mod protocol_stats;
 
 pub use match_state::*;
-pub use config::*;
\ No newline at end of file
+pub use config::*;
+pub use side_pool::*;
+pub use queue_ticket::*;
+pub use time_control::*;
+pub use variant::*;
+pub use game_log::*;
+pub use channel::*;
+pub use result_reason::*;
+pub use attestation::*;
+pub use result_proposal::*;
+pub use arbiter::*;
+pub use oracle_bond::*;
+pub use pending_withdrawal::*;
+pub use proposal::*;
+pub use role::*;
+pub use fee_split::*;
+pub use referral::*;
+pub use protocol_stats::*;
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::MatchConfig;
+use crate::{Role, RoleAssignment};
+
+/// Stake a registered oracle locks to be allowed to settle matches, held in its bond vault.
+#[account]
+#[derive(InitSpace)]
+pub struct OracleBond {
+    pub oracle: Pubkey,
+    pub amount: u64, // slashable lamports, the vault also holds its own rent minimum
+    pub unbond_requested_at: i64, // 0 while bonded, the oracle can't settle once unbonding starts
+    pub open_proposals: u32, // proposals that could still be overturned and slashed
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+impl OracleBond {
+    pub fn is_active(&self, config: &MatchConfig) -> bool {
+        self.unbond_requested_at == 0 && self.amount >= config.min_oracle_bond
+    }
+
+    /// The admin can always settle, anyone else has to hold the oracle role and an active bond.
+    pub fn require_bonded(
+        config: &MatchConfig,
+        key: &Pubkey,
+        bond: &Option<Account<OracleBond>>,
+        role: &Option<Account<RoleAssignment>>,
+    ) -> Result<()> {
+        if *key == config.authority {
+            return Ok(());
+        }
+        require!(
+            config.has_role(Role::Oracle, key, role),
+            ErrorCode::UnknownOracleError
+        );
+
+        let bond = bond.as_ref().ok_or(ErrorCode::OracleNotBondedError)?;
+        require!(
+            bond.oracle == *key && bond.is_active(config),
+            ErrorCode::OracleNotBondedError
+        );
+
+        Ok(())
+    }
+
+    pub fn slash_amount(&self, config: &MatchConfig) -> u64 {
+        (self.amount as u128 * config.oracle_slash_bps as u128 / 10_000) as u64
+    }
+}
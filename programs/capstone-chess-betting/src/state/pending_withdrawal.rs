@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+/// Treasury withdrawal waiting out the timelock, see queue_withdrawal.
+#[account]
+#[derive(InitSpace)]
+pub struct PendingWithdrawal {
+    pub id: u64,
+    pub queued_by: Pubkey, // paid the rent, gets it back when the withdrawal is executed or cancelled
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub eta: i64,
+    pub bump: u8,
+}
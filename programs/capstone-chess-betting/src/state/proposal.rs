@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::MAX_ADMIN_SIGNERS;
+
+/// Most accounts an admin proposal's instruction can take.
+pub const MAX_PROPOSAL_ACCOUNTS: usize = 16;
+
+/// Most instruction data bytes an admin proposal can carry.
+pub const MAX_PROPOSAL_DATA: usize = 256;
+
+/// Admin instruction waiting for approvals from the signer set, executed as the admin PDA, see create_proposal.
+#[account]
+#[derive(InitSpace)]
+pub struct Proposal {
+    pub id: u64,
+    pub proposer: Pubkey, // paid the rent, gets it back when the proposal is executed or cancelled
+    #[max_len(MAX_PROPOSAL_ACCOUNTS)]
+    pub accounts: Vec<ProposalAccount>,
+    #[max_len(MAX_PROPOSAL_DATA)]
+    pub data: Vec<u8>,
+    #[max_len(MAX_ADMIN_SIGNERS)]
+    pub approvals: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct ProposalAccount {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+impl Proposal {
+    /// Approvals from keys that are still in the signer set.
+    pub fn valid_approvals(&self, signers: &[Pubkey]) -> usize {
+        self.approvals.iter().filter(|key| signers.contains(key)).count()
+    }
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+/// Running protocol totals for dashboards, updated by the instructions that create, settle or cancel matches
+/// and by treasury withdrawals. All amounts are in lamports.
+/// The totals are split over `STATS_SHARDS` accounts so concurrent matches don't all write to one,
+/// each instruction counts into whichever shard it is passed and dashboards sum them.
+#[account]
+#[derive(InitSpace)]
+pub struct ProtocolStats {
+    pub shard: u8,
+    pub matches_created: u64,
+    pub matches_accepted: u64,
+    pub matches_cancelled: u64,
+    pub matches_completed: u64,
+    pub matches_drawn: u64,
+    pub total_wagered: u64, // both players' stakes, counted as each one is deposited
+    pub win_fees: u64, // fees kept by the treasury, after referral rebates
+    pub draw_fees: u64,
+    pub cancel_penalties: u64, // the treasury's share, after referral rebates
+    pub cancel_compensation: u64, // the abandoned opponents' share
+    pub leftover_swept: u64, // vault lamports swept to the treasury beyond the fees above
+    pub referral_rebates: u64,
+    pub side_pool_fees: u64,
+    pub total_withdrawn: u64, // withdrawals and fee distributions
+    pub fees_distributed: u64, // the fee split's share of total_withdrawn
+    pub bump: u8,
+}
+
+impl ProtocolStats {
+    /// Stats are informational, they saturate rather than fail the instruction that updates them.
+    pub fn add(counter: &mut u64, amount: u64) {
+        *counter = counter.saturating_add(amount);
+    }
+}
@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+use crate::TimeControl;
+
+#[account]
+#[derive(InitSpace)]
+pub struct QueueTicket {
+    pub player: Pubkey,
+    pub bet_amount: u64,
+    pub time_control: TimeControl,
+    pub color_commitment: [u8; 32], // sha256 of the player's colour secret, revealed once paired
+    pub created_at: i64,
+    pub bump: u8,
+    pub vault_bump: u8,
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+
+/// Who referred a player, recorded the first time the player names a referrer and never changed after.
+#[account]
+#[derive(InitSpace)]
+pub struct Referral {
+    pub player: Pubkey,
+    pub referrer: Pubkey,
+    pub bump: u8,
+}
+
+/// A referrer's share of protocol fees, the lamports sit on this account until claimed.
+#[account]
+#[derive(InitSpace)]
+pub struct ReferrerRewards {
+    pub referrer: Pubkey,
+    pub claimable: u64,
+    pub total_earned: u64,
+    pub bump: u8,
+}
+
+impl Referral {
+    /// Fills in a freshly created referral, or keeps the one already on record,
+    /// and returns the referrer to copy into the match.
+    pub fn record(
+        referral: &mut Option<Account<Referral>>,
+        rewards: &Option<Account<ReferrerRewards>>,
+        player: Pubkey,
+        referrer: Option<Pubkey>,
+        bump: Option<u8>,
+    ) -> Result<Option<Pubkey>> {
+        let Some(referral) = referral.as_mut() else {
+            require!(referrer.is_none(), ErrorCode::InvalidReferralError);
+            return Ok(None);
+        };
+
+        if referral.player == Pubkey::default() {
+            // created without a referrer, nothing to record until the player names one
+            let Some(referrer) = referrer else {
+                return Ok(None);
+            };
+            require_keys_neq!(referrer, player, ErrorCode::InvalidReferralError);
+            // the referrer has to be registered so settlement always has somewhere to pay the rebate
+            require!(
+                rewards.as_ref().is_some_and(|rewards| rewards.referrer == referrer),
+                ErrorCode::InvalidReferralError
+            );
+
+            referral.player = player;
+            referral.referrer = referrer;
+            referral.bump = bump.ok_or(ErrorCode::InvalidReferralError)?;
+        }
+
+        Ok(Some(referral.referrer))
+    }
+}
+
+impl ReferrerRewards {
+    pub fn credit(&mut self, amount: u64) -> Result<()> {
+        self.claimable = self.claimable.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+        self.total_earned = self.total_earned.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+use crate::ResultReason;
+
+/// Result waiting out its challenge window before it can be paid, see propose_result.
+#[account]
+#[derive(InitSpace)]
+pub struct ResultProposal {
+    pub match_account: Pubkey,
+    pub proposer: Pubkey, // paid the rent, gets it back once the proposal is finalized or resolved
+    pub winner: Option<Pubkey>,
+    pub reason: ResultReason,
+    pub challenge_deadline: i64,
+    pub disputer: Option<Pubkey>,
+    pub dispute_bond: u64, // held in the bond vault while a dispute is open
+    pub escalated: bool, // an arbiter panel has the case, the admin can't resolve it alone
+    pub escalation_used: bool, // a dispute gets one panel, if its case expires the admin decides
+    pub bump: u8,
+    pub bond_vault_bump: u8,
+}
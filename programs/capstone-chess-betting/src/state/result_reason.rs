@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::BoardStatus;
+use crate::SideOutcome;
+
+/// Why a game ended, as reported by whoever settles it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ResultReason {
+    Checkmate,
+    Resignation,
+    Timeout,
+    Abandonment,
+    Agreement,
+    Stalemate,
+    Repetition,
+    InsufficientMaterial,
+    FiftyMoveRule,
+    Adjudication,
+    FairPlayVoid,
+}
+
+impl Space for ResultReason {
+    const INIT_SPACE: usize = 1;
+}
+
+impl From<BoardStatus> for Option<ResultReason> {
+    fn from(status: BoardStatus) -> Self {
+        match status {
+            BoardStatus::Ongoing => None,
+            BoardStatus::Checkmate => Some(ResultReason::Checkmate),
+            BoardStatus::Stalemate => Some(ResultReason::Stalemate),
+            BoardStatus::FiftyMoveRule => Some(ResultReason::FiftyMoveRule),
+            BoardStatus::ThreefoldRepetition => Some(ResultReason::Repetition),
+            BoardStatus::InsufficientMaterial => Some(ResultReason::InsufficientMaterial),
+            BoardStatus::Timeout => Some(ResultReason::Timeout),
+        }
+    }
+}
+
+/// One player's claim in report_result, reports only agree when both fields match.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct ResultReport {
+    pub outcome: SideOutcome,
+    pub reason: ResultReason,
+}
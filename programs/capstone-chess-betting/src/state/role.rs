@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+/// What a role assignment lets its holder do, the config authority holds every role.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Role {
+    Admin, // config changes, granting and revoking roles, unpausing, until a signer set is configured
+    Oracle, // settling matches and signing attestations, once bonded
+    Pauser, // pausing new matches
+    Treasurer, // queueing and cancelling treasury withdrawals, until a signer set is configured
+    FeeManager, // setting fees, until a signer set is configured
+}
+
+impl Role {
+    /// Roles whose powers pass to the admin signer set once one is configured.
+    pub fn needs_signer_set(&self) -> bool {
+        matches!(self, Role::Admin | Role::Treasurer | Role::FeeManager)
+    }
+}
+
+impl Space for Role {
+    const INIT_SPACE: usize = 1;
+}
+
+/// One role held by one key, see grant_role.
+#[account]
+#[derive(InitSpace)]
+pub struct RoleAssignment {
+    pub role: Role,
+    pub holder: Pubkey,
+    pub granted_by: Pubkey, // paid the rent, gets it back when the role is revoked
+    pub bump: u8,
+}
+
+impl RoleAssignment {
+    pub fn grants(&self, role: Role, key: &Pubkey) -> bool {
+        self.role == role && self.holder == *key
+    }
+}
@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+
+#[account]
+#[derive(InitSpace)]
+pub struct SidePool {
+    pub match_account: Pubkey,
+    pub player_a: Pubkey,
+    pub player_b: Pubkey,
+    pub payer: Pubkey, // funded the pool and its vault's rent, gets both back in close_side_pool
+    pub closes_at: i64,
+    pub totals: [u64; 3], // indexed by SideOutcome
+    pub result: Option<SideOutcome>,
+    pub payout_pool: u64, // what winning bettors share, after the protocol fee
+    pub open_bets: u32, // side bets not yet claimed, the pool can only be closed at zero
+    pub status: SidePoolStatus,
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct SideBet {
+    pub side_pool: Pubkey,
+    pub bettor: Pubkey,
+    pub outcome: SideOutcome,
+    pub amount: u64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Copy)]
+pub enum SideOutcome {
+    PlayerA,
+    PlayerB,
+    Draw,
+}
+
+impl Space for SideOutcome {
+    const INIT_SPACE: usize = 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Copy)]
+pub enum SidePoolStatus {
+    Open,
+    Resolved,
+    Void, // match cancelled or nobody backed the result, every bet is refunded
+}
+
+impl Space for SidePoolStatus {
+    const INIT_SPACE: usize = 1;
+}
+
+impl SidePool {
+    pub fn total(&self) -> Result<u64> {
+        self.totals
+            .iter()
+            .try_fold(0u64, |acc, x| acc.checked_add(*x))
+            .ok_or(error!(ErrorCode::MathOverflow))
+    }
+
+    /// Settles the pool on the match result and returns the protocol fee to move to the treasury.
+    pub fn resolve(&mut self, outcome: SideOutcome, fee_bps: u16) -> Result<u64> {
+        require!(self.status == SidePoolStatus::Open, ErrorCode::InvalidSidePool);
+
+        let total = self.total()?;
+        if self.totals[outcome as usize] == 0 {
+            self.void()?;
+            return Ok(0);
+        }
+
+        let fee = (total as u128)
+            .checked_mul(fee_bps as u128)
+            .and_then(|x| x.checked_div(10_000))
+            .ok_or(ErrorCode::MathOverflow)? as u64;
+
+        self.payout_pool = total.checked_sub(fee).ok_or(ErrorCode::MathOverflow)?;
+        self.result = Some(outcome);
+        self.status = SidePoolStatus::Resolved;
+
+        Ok(fee)
+    }
+
+    pub fn void(&mut self) -> Result<()> {
+        require!(self.status == SidePoolStatus::Open, ErrorCode::InvalidSidePool);
+        self.payout_pool = self.total()?;
+        self.status = SidePoolStatus::Void;
+        Ok(())
+    }
+
+    /// Amount owed to a bet once the pool is settled, zero for a losing bet.
+    pub fn payout_for(&self, bet: &SideBet) -> Result<u64> {
+        match self.status {
+            SidePoolStatus::Open => err!(ErrorCode::SidePoolNotSettled),
+            SidePoolStatus::Void => Ok(bet.amount),
+            SidePoolStatus::Resolved => {
+                if self.result != Some(bet.outcome) {
+                    return Ok(0);
+                }
+                let share = (bet.amount as u128)
+                    .checked_mul(self.payout_pool as u128)
+                    .and_then(|x| x.checked_div(self.totals[bet.outcome as usize] as u128))
+                    .ok_or(ErrorCode::MathOverflow)?;
+                Ok(share as u64)
+            }
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::MatchConfig;
+
+/// Clock settings agreed for a match, all values in seconds.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct TimeControl {
+    pub base_seconds: u32,
+    pub increment_seconds: u32,
+    pub delay_seconds: u32,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum TimeCategory {
+    Bullet,
+    Blitz,
+    Rapid,
+    Classical,
+}
+
+impl TimeControl {
+    /// Estimated length of one player's clock over a 40 move game, as used for rating pools.
+    pub fn estimated_seconds(&self) -> u64 {
+        self.base_seconds as u64 + 40 * (self.increment_seconds as u64 + self.delay_seconds as u64)
+    }
+
+    pub fn category(&self) -> TimeCategory {
+        match self.estimated_seconds() {
+            0..180 => TimeCategory::Bullet,
+            180..480 => TimeCategory::Blitz,
+            480..1500 => TimeCategory::Rapid,
+            _ => TimeCategory::Classical,
+        }
+    }
+
+    pub fn validate(&self, config: &MatchConfig) -> Result<()> {
+        require!(
+            self.base_seconds >= config.min_base_seconds
+                && self.base_seconds <= config.max_base_seconds
+                && self.increment_seconds <= config.max_increment_seconds
+                && self.delay_seconds <= config.max_delay_seconds,
+            ErrorCode::InvalidTimeControlError
+        );
+        Ok(())
+    }
+
+    /// Bytes used to bucket queue tickets by time control.
+    pub fn to_seed_bytes(&self) -> [u8; 12] {
+        let mut bytes = [0u8; 12];
+        bytes[..4].copy_from_slice(&self.base_seconds.to_le_bytes());
+        bytes[4..8].copy_from_slice(&self.increment_seconds.to_le_bytes());
+        bytes[8..].copy_from_slice(&self.delay_seconds.to_le_bytes());
+        bytes
+    }
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Variant {
+    Standard,
+    Chess960,
+    Crazyhouse,
+    KingOfTheHill,
+    ThreeCheck,
+    Antichess,
+    Atomic,
+    Horde,
+    RacingKings,
+}
+
+impl Space for Variant {
+    const INIT_SPACE: usize = 1;
+}
+
+/// Number of distinct Chess960 starting positions, indexed 0..960 in Scharnagl numbering.
+pub const CHESS960_POSITIONS: u16 = 960;
@@ -0,0 +1,72 @@
+//! Cancel penalty: grows with how long the game has run, is capped, and is split with the opponent.
+
+use capstone_chess_betting::{DEFAULT_CANCEL_PENALTY_STEP, DEFAULT_MAX_CANCEL_PENALTY_BPS};
+
+mod common;
+
+use common::{config, SOL};
+
+#[test]
+fn base_penalty_right_after_acceptance() {
+    // 1% of 1 SOL, three quarters of it to the opponent
+    assert_eq!(config().cancel_penalty(SOL, 0), (7_500_000, 2_500_000));
+}
+
+#[test]
+fn escalates_once_per_full_step() {
+    let config = config();
+
+    assert_eq!(
+        config.cancel_penalty(SOL, DEFAULT_CANCEL_PENALTY_STEP - 1),
+        config.cancel_penalty(SOL, 0)
+    );
+    // 2% after one step, 3% after two
+    assert_eq!(
+        config.cancel_penalty(SOL, DEFAULT_CANCEL_PENALTY_STEP),
+        (15_000_000, 5_000_000)
+    );
+    assert_eq!(
+        config.cancel_penalty(SOL, 2 * DEFAULT_CANCEL_PENALTY_STEP),
+        (22_500_000, 7_500_000)
+    );
+}
+
+#[test]
+fn capped_at_the_max_penalty() {
+    let config = config();
+    let max_penalty = SOL * DEFAULT_MAX_CANCEL_PENALTY_BPS as u64 / 10_000;
+
+    for elapsed in [
+        4 * DEFAULT_CANCEL_PENALTY_STEP,
+        100 * DEFAULT_CANCEL_PENALTY_STEP,
+        i64::MAX,
+    ] {
+        let (compensation, protocol) = config.cancel_penalty(SOL, elapsed);
+        assert_eq!(compensation + protocol, max_penalty);
+    }
+}
+
+#[test]
+fn clock_skew_before_acceptance_counts_as_no_time() {
+    let config = config();
+    assert_eq!(
+        config.cancel_penalty(SOL, -60),
+        config.cancel_penalty(SOL, 0)
+    );
+}
+
+#[test]
+fn split_follows_the_opponent_share() {
+    let mut config = config();
+
+    config.cancel_opponent_share_bps = 10_000;
+    assert_eq!(config.cancel_penalty(SOL, 0), (10_000_000, 0));
+
+    config.cancel_opponent_share_bps = 0;
+    assert_eq!(config.cancel_penalty(SOL, 0), (0, 10_000_000));
+
+    // rounding leftovers go to the protocol, the two parts always add up to the penalty
+    config.cancel_opponent_share_bps = 3_333;
+    let (compensation, protocol) = config.cancel_penalty(999, 0);
+    assert_eq!((compensation, protocol), (2, 7));
+}
@@ -0,0 +1,15 @@
+//! Fixtures shared by the config tests.
+#![allow(dead_code)] // each test crate only uses part of it
+
+use anchor_lang::prelude::Pubkey;
+use capstone_chess_betting::MatchConfig;
+
+pub const NOW: i64 = 1_700_000_000;
+pub const SOL: u64 = 1_000_000_000;
+
+/// A config with the default settings, in the withdrawal epoch that contains `NOW`.
+pub fn config() -> MatchConfig {
+    let mut config = MatchConfig::new(Pubkey::new_unique(), 0, 0);
+    config.current_epoch = NOW / config.withdrawal_epoch;
+    config
+}
@@ -0,0 +1,52 @@
+//! Fee split shares: validation of the configured shares and each beneficiary's cut of a distribution.
+
+use anchor_lang::prelude::Pubkey;
+use capstone_chess_betting::{FeeShare, FeeSplit};
+
+fn split(shares: &[(Pubkey, u16)]) -> anchor_lang::Result<FeeSplit> {
+    let mut split = FeeSplit {
+        beneficiaries: Vec::new(),
+        reserve: 0,
+        total_distributed: 0,
+        bump: 0,
+    };
+    split.configure(
+        shares
+            .iter()
+            .map(|&(beneficiary, share_bps)| FeeShare { beneficiary, share_bps })
+            .collect(),
+        0,
+    )?;
+    Ok(split)
+}
+
+#[test]
+fn shares_must_add_up_to_the_whole() {
+    let (team, insurance) = (Pubkey::new_unique(), Pubkey::new_unique());
+    assert!(split(&[(team, 7000)]).is_err());
+    assert!(split(&[(team, 7000), (insurance, 3001)]).is_err());
+    assert!(split(&[(team, 5000), (team, 5000)]).is_err());
+    assert!(split(&[]).is_err());
+    assert!(split(&[(team, 7000), (insurance, 3000)]).is_ok());
+}
+
+#[test]
+fn each_share_rounds_down() {
+    let split = split(&[(Pubkey::new_unique(), 7000), (Pubkey::new_unique(), 3000)]).unwrap();
+    let shares: Vec<u64> = split.beneficiaries.iter().map(|b| b.share_of(1_000_001)).collect();
+
+    assert_eq!(shares, vec![700_000, 300_000]);
+}
+
+#[test]
+fn reconfiguring_keeps_running_totals() {
+    let (team, insurance) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut split = split(&[(team, 7000), (insurance, 3000)]).unwrap();
+    split.beneficiaries[0].total_received = 42;
+
+    split
+        .configure(vec![FeeShare { beneficiary: team, share_bps: 10_000 }], 0)
+        .unwrap();
+    assert_eq!(split.beneficiaries.len(), 1);
+    assert_eq!(split.beneficiaries[0].total_received, 42);
+}
@@ -0,0 +1,149 @@
+//! Move generation checked against the standard perft positions from the Chess Programming Wiki.
+
+use capstone_chess_betting::chess::*;
+
+fn perft(board: &Board, depth: u32) -> u64 {
+    let moves = board.legal_moves();
+    if depth == 1 {
+        return moves.len() as u64;
+    }
+    moves
+        .as_slice()
+        .iter()
+        .map(|mv| {
+            let mut next = *board;
+            next.make_move(*mv);
+            perft(&next, depth - 1)
+        })
+        .sum()
+}
+
+fn check(fen: &str, expected: &[u64]) {
+    let board = Board::from_fen(fen).expect("valid fen");
+    for (depth, nodes) in expected.iter().enumerate() {
+        assert_eq!(perft(&board, depth as u32 + 1), *nodes, "{fen} at depth {}", depth + 1);
+    }
+}
+
+#[test]
+fn perft_start_position() {
+    let board = Board::start_position();
+    assert_eq!(Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"), Some(board));
+    for (depth, nodes) in [20, 400, 8_902, 197_281].iter().enumerate() {
+        assert_eq!(perft(&board, depth as u32 + 1), *nodes);
+    }
+}
+
+#[test]
+fn perft_kiwipete() {
+    check(
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        &[48, 2_039, 97_862],
+    );
+}
+
+#[test]
+fn perft_position_3() {
+    check("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1", &[14, 191, 2_812, 43_238]);
+}
+
+#[test]
+fn perft_position_4() {
+    check(
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        &[6, 264, 9_467],
+    );
+    check(
+        "r2q1rk1/pP1p2pp/Q4n2/bbp1p3/Np6/1B3NBn/pPPP1PPP/R3K2R b KQ - 0 1",
+        &[6, 264, 9_467],
+    );
+}
+
+#[test]
+fn perft_position_5() {
+    check("rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8", &[44, 1_486, 62_379]);
+}
+
+#[test]
+fn perft_position_6() {
+    check(
+        "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10",
+        &[46, 2_079, 89_890],
+    );
+}
+
+fn play(board: &mut Board, moves: &[&str]) -> Vec<u64> {
+    let mut history = vec![board.hash()];
+    for uci in moves {
+        let mv = Move::from_uci(uci).unwrap();
+        assert!(board.is_legal(mv), "{uci} should be legal");
+        board.make_move(mv);
+        history.push(board.hash());
+    }
+    history.pop();
+    history
+}
+
+#[test]
+fn detects_checkmate() {
+    let mut board = Board::start_position();
+    let history = play(&mut board, &["f2f3", "e7e5", "g2g4", "d8h4"]);
+    assert_eq!(board.status(&history), GameStatus::Checkmate { winner: Color::Black });
+}
+
+#[test]
+fn detects_stalemate() {
+    let board = Board::from_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1").unwrap();
+    assert!(!board.in_check());
+    assert_eq!(board.status(&[]), GameStatus::Stalemate);
+}
+
+#[test]
+fn detects_threefold_repetition() {
+    let mut board = Board::start_position();
+    let history = play(
+        &mut board,
+        &["g1f3", "g8f6", "f3g1", "f6g8", "g1f3", "g8f6", "f3g1", "f6g8"],
+    );
+    assert_eq!(board.status(&history), GameStatus::ThreefoldRepetition);
+}
+
+#[test]
+fn detects_fifty_move_rule() {
+    let board = Board::from_fen("8/8/4k3/8/8/3QK3/8/8 w - - 100 80").unwrap();
+    assert_eq!(board.status(&[]), GameStatus::FiftyMoveRule);
+}
+
+#[test]
+fn detects_insufficient_material() {
+    assert!(Board::from_fen("8/8/4k3/8/8/3NK3/8/8 w - - 0 1").unwrap().is_insufficient_material());
+    // bishops on same coloured squares can never mate, on opposite colours they can
+    assert!(Board::from_fen("8/3b4/4k3/8/8/3BK3/8/8 w - - 0 1").unwrap().is_insufficient_material());
+    assert!(!Board::from_fen("8/2b5/4k3/8/8/3BK3/8/8 w - - 0 1").unwrap().is_insufficient_material());
+    assert!(!Board::from_fen("8/8/4k3/8/8/3PK3/8/8 w - - 0 1").unwrap().is_insufficient_material());
+}
+
+#[test]
+fn rejects_illegal_moves() {
+    let board = Board::start_position();
+    assert!(!board.is_legal(Move::from_uci("e2e5").unwrap()));
+    assert!(!board.is_legal(Move::from_uci("e1e2").unwrap()));
+
+    // the pinned knight can't move, and a pawn reaching the last rank must promote
+    let pinned = Board::from_fen("4k3/4r3/8/8/8/8/4N3/4K3 w - - 0 1").unwrap();
+    assert!(!pinned.is_legal(Move::from_uci("e2c3").unwrap()));
+    let promotion = Board::from_fen("4k3/P7/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    assert!(!promotion.is_legal(Move::from_uci("a7a8").unwrap()));
+    assert!(promotion.is_legal(Move::from_uci("a7a8n").unwrap()));
+}
+
+#[test]
+fn compact_board_round_trips() {
+    let mut board = Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1").unwrap();
+    board.make_move(Move::from_uci("a2a4").unwrap());
+    assert_eq!(board.en_passant, Some(16), "b4 pawn can take en passant on a3");
+    assert_eq!(Board::unpack(&board.pack()), board);
+
+    let mv = Move::new(52, 60, Some(PieceKind::Queen));
+    assert_eq!(Move::unpack(mv.pack()), Some(mv));
+}
@@ -0,0 +1,102 @@
+//! Treasury withdrawal limits: tightening applies at once, loosening waits out the withdrawal delay.
+
+use anchor_lang::prelude::Pubkey;
+use capstone_chess_betting::{
+    WithdrawDestination, DEFAULT_WITHDRAWAL_DELAY, DEFAULT_WITHDRAWAL_EPOCH,
+    DEFAULT_WITHDRAWAL_EPOCH_CAP,
+};
+
+mod common;
+
+use common::{config, NOW, SOL};
+
+#[test]
+fn lower_cap_applies_at_once() {
+    let mut config = config();
+    config.set_withdrawal_limits(DEFAULT_WITHDRAWAL_EPOCH, 10 * SOL, NOW).unwrap();
+
+    assert_eq!(config.withdrawal_epoch_cap, 10 * SOL);
+    assert!(config.pending_withdrawal_limits.is_none());
+    assert!(config.record_epoch_withdrawal(10 * SOL + 1, NOW).is_err());
+    config.record_epoch_withdrawal(10 * SOL, NOW).unwrap();
+}
+
+#[test]
+fn higher_cap_waits_for_the_delay() {
+    let mut config = config();
+    let active_at = NOW + DEFAULT_WITHDRAWAL_DELAY;
+    config.set_withdrawal_limits(DEFAULT_WITHDRAWAL_EPOCH, u64::MAX, NOW).unwrap();
+
+    assert_eq!(config.withdrawal_epoch_cap, DEFAULT_WITHDRAWAL_EPOCH_CAP);
+    assert_eq!(
+        config.pending_withdrawal_limits.map(|pending| pending.active_at),
+        Some(active_at)
+    );
+    assert!(config
+        .record_epoch_withdrawal(DEFAULT_WITHDRAWAL_EPOCH_CAP + 1, active_at - 1)
+        .is_err());
+
+    config
+        .record_epoch_withdrawal(DEFAULT_WITHDRAWAL_EPOCH_CAP + 1, active_at)
+        .unwrap();
+    assert_eq!(config.withdrawal_epoch_cap, u64::MAX);
+    assert!(config.pending_withdrawal_limits.is_none());
+}
+
+#[test]
+fn shorter_epoch_waits_for_the_delay() {
+    let mut config = config();
+    config.set_withdrawal_limits(60, DEFAULT_WITHDRAWAL_EPOCH_CAP, NOW).unwrap();
+
+    assert_eq!(config.withdrawal_epoch, DEFAULT_WITHDRAWAL_EPOCH);
+    assert!(config.pending_withdrawal_limits.is_some());
+}
+
+#[test]
+fn tightening_drops_a_pending_loosening() {
+    let mut config = config();
+    config.set_withdrawal_limits(DEFAULT_WITHDRAWAL_EPOCH, u64::MAX, NOW).unwrap();
+    config.set_withdrawal_limits(DEFAULT_WITHDRAWAL_EPOCH, SOL, NOW + 1).unwrap();
+
+    assert!(config.pending_withdrawal_limits.is_none());
+    assert!(config
+        .record_epoch_withdrawal(SOL + 1, NOW + DEFAULT_WITHDRAWAL_DELAY)
+        .is_err());
+}
+
+#[test]
+fn longer_epoch_keeps_counting_what_was_withdrawn() {
+    let mut config = config();
+    config.record_epoch_withdrawal(60 * SOL, NOW).unwrap();
+    config.set_withdrawal_limits(2 * DEFAULT_WITHDRAWAL_EPOCH, DEFAULT_WITHDRAWAL_EPOCH_CAP, NOW).unwrap();
+
+    assert_eq!(config.withdrawal_epoch, 2 * DEFAULT_WITHDRAWAL_EPOCH);
+    assert!(config.record_epoch_withdrawal(40 * SOL + 1, NOW).is_err());
+    config.record_epoch_withdrawal(40 * SOL, NOW).unwrap();
+}
+
+#[test]
+fn destinations_activate_after_their_delay() {
+    let mut config = config();
+    let destination = Pubkey::new_unique();
+    config.withdraw_destinations.push(WithdrawDestination {
+        key: destination,
+        active_at: NOW + DEFAULT_WITHDRAWAL_DELAY,
+    });
+
+    assert!(!config.is_withdraw_destination(&destination, NOW));
+    assert!(!config.is_withdraw_destination(&Pubkey::new_unique(), NOW + DEFAULT_WITHDRAWAL_DELAY));
+    assert!(config.is_withdraw_destination(&destination, NOW + DEFAULT_WITHDRAWAL_DELAY));
+}
+
+#[test]
+fn allowance_is_what_the_cap_leaves_this_epoch() {
+    let mut config = config();
+    config.record_epoch_withdrawal(60 * SOL, NOW).unwrap();
+
+    assert_eq!(config.epoch_allowance(NOW), DEFAULT_WITHDRAWAL_EPOCH_CAP - 60 * SOL);
+    assert_eq!(
+        config.epoch_allowance(NOW + DEFAULT_WITHDRAWAL_EPOCH),
+        DEFAULT_WITHDRAWAL_EPOCH_CAP
+    );
+}
@@ -1,9 +1,10 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { CapstoneChessBetting } from "../target/types/capstone_chess_betting";
-import { Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram } from "@solana/web3.js";
+import { Ed25519Program, Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram } from "@solana/web3.js";
 import authWallet from '../auth-wallet.json'; // Import the auth wallet
 import { assert, expect } from "chai";
+import { createHash, randomBytes } from "crypto";
 
 
 
@@ -23,11 +24,12 @@ describe("capstone-chess-betting", () => {
   let matchStatus;
   let matchAccount: PublicKey;
   let vault: PublicKey;
-  let matchDuration: number;
+  let timeControl: { baseSeconds: number; incrementSeconds: number; delaySeconds: number };
   let code: string;
 
   const authority = Keypair.fromSecretKey(Uint8Array.from(authWallet));
   let treasuryPda: PublicKey;
+  const [stats] = PublicKey.findProgramAddressSync([Buffer.from("stats"), Buffer.from([0])], program.programId);
   let config: PublicKey;
   let configBump: number;
   let treasuryBump: number;
@@ -37,6 +39,11 @@ describe("capstone-chess-betting", () => {
   let playerA: anchor.web3.Keypair;
   let playerB: anchor.web3.Keypair;
 
+  // Player A commits to a colour secret, player B answers with a random value
+  const colorSecret = randomBytes(32);
+  const colorCommitment = Array.from(createHash("sha256").update(colorSecret).digest());
+  const playerBRandom = Array.from(randomBytes(32));
+
 
 
   describe("A complete match", () => {
@@ -87,15 +94,18 @@ describe("capstone-chess-betting", () => {
         })
         .signers([authority])
         .rpc();
+      await program.methods.initStats(0).accountsPartial({ payer: wallet.publicKey, stats }).rpc();
 
     });
 
     it("Initialize Match", async () => {
-      matchDuration = 60;
+      timeControl = { baseSeconds: 60, incrementSeconds: 0, delaySeconds: 0 };
       const tx = await program.methods
-        .initializeMatch(seed, code, matchDuration, betAmount, null, null)
+        .initializeMatch(seed, code, timeControl, { standard: {} }, colorCommitment, betAmount, null, null, null)
         .accountsPartial({
+          stats,
           playerA: playerA.publicKey,
+          payer: playerA.publicKey,
           matchAccount: matchAccount,
           vault: vault,
           systemProgram: SystemProgram.programId,
@@ -105,7 +115,7 @@ describe("capstone-chess-betting", () => {
       const matchData = await program.account.matchState.fetch(matchAccount);
       const vaultAccount = await connection.getAccountInfo(vault);
       matchStatus = matchData.status;
-      assert.ok(matchData.matchDuration === matchDuration);
+      assert.ok(matchData.timeControl.baseSeconds === timeControl.baseSeconds);
       assert.ok(matchData.betAmount.eq(betAmount));
       assert.ok(matchData.playerA.equals(playerA.publicKey));
       assert.ok(matchData.playerB === null);
@@ -115,8 +125,9 @@ describe("capstone-chess-betting", () => {
 
     it("Accept Match", async () => {
       const tx = await program.methods
-        .acceptMatch(code)
+        .acceptMatch(code, { standard: {} }, playerBRandom, null)
         .accountsPartial({
+          stats,
           playerB: playerB.publicKey,
           playerA: playerA.publicKey,
           matchAccount: matchAccount,
@@ -134,15 +145,38 @@ describe("capstone-chess-betting", () => {
       assert.ok(matchData.playerA.equals(playerA.publicKey));
     });
 
+    it("Reveal Colors", async () => {
+      try {
+        await program.methods
+          .revealColors(code, Array.from(randomBytes(32)))
+          .accountsPartial({ player: playerA.publicKey, matchAccount })
+          .signers([playerA])
+          .rpc();
+        assert.fail("A secret that doesn't match the commitment should be rejected");
+      } catch (error) {
+        expect(error.message).to.include("InvalidRevealError");
+      }
+
+      await program.methods
+        .revealColors(code, Array.from(colorSecret))
+        .accountsPartial({ player: playerA.publicKey, matchAccount })
+        .signers([playerA])
+        .rpc();
+      const matchData = await program.account.matchState.fetch(matchAccount);
+      assert.ok(matchData.white.equals(playerA.publicKey) || matchData.white.equals(playerB.publicKey));
+    });
+
     it("Final Payouts", async () => {
       winner = playerA.publicKey; // Simulating player A as the winner
       const initialBalancePlayerA = await connection.getBalance(playerA.publicKey);
+      const matchRent = await connection.getBalance(matchAccount);
       const initialTreasuryBalance = await connection.getBalance(treasuryPda);
       const vaultAccount = await connection.getAccountInfo(vault);
       const vaultRent = await connection.getMinimumBalanceForRentExemption(vaultAccount.data.length);
       const tx = await program.methods
-        .finalPayouts(code, winner)
+        .finalPayouts(code, winner, { checkmate: {} })
         .accountsPartial({
+          stats,
           authority: authority.publicKey,
           playerA: playerA.publicKey,
           playerB: playerB.publicKey,
@@ -177,35 +211,109 @@ describe("capstone-chess-betting", () => {
       const playerAInfo = await connection.getAccountInfo(playerA.publicKey);
       assert.ok(treasuryAccount, "Treasury account should exist");
       assert.ok(playerAInfo, "Player A account should exist");
-      assert.equal(finalBalancePlayerA, initialBalancePlayerA + winningAmount.toNumber(), "Player A should receive the winning amount");
-      assert.ok(treasuryAccount.lamports > initialTreasuryBalance + feeAmount.toNumber(), "Treasury should have at least receive the fee amount");
+      assert.equal(finalBalancePlayerA, initialBalancePlayerA + winningAmount.toNumber() + matchRent, "Player A should receive the winning amount and the match rent");
+      assert.equal(treasuryAccount.lamports, initialTreasuryBalance + feeAmount.toNumber(), "Treasury should receive only the fee amount");
     })
 
-    it("Withdraw from Treasury", async () => {
-      const initialTreasuryBalance = await connection.getBalance(treasuryPda);
-      const treasuryAccount = await connection.getAccountInfo(treasuryPda);
-      const authorityInfo = await connection.getAccountInfo(authority.publicKey);
-      const authorityInitialBalance = authorityInfo ? authorityInfo.lamports : 0;
-      const treasuryRent = await connection.getMinimumBalanceForRentExemption(treasuryAccount.data.length);
-      const tx = await program.methods
-        .withdrawFromTreasury()
-        .accountsPartial({
-          authority: authority.publicKey,
-          treasuryPda: treasuryPda,
-          config: config,
-          systemProgram: SystemProgram.programId,
-        })
+    it("Tracks protocol stats", async () => {
+      const statsData = await program.account.protocolStats.fetch(stats);
+      const totalBetAmount = betAmount.mul(new anchor.BN(2));
+      assert.equal(statsData.matchesCreated.toNumber(), 1);
+      assert.equal(statsData.matchesAccepted.toNumber(), 1);
+      assert.equal(statsData.matchesCompleted.toNumber(), 1);
+      assert.ok(statsData.totalWagered.eq(totalBetAmount));
+      assert.ok(statsData.winFees.eq(totalBetAmount.div(new anchor.BN(200))), "The 0.5% fee should be attributed");
+      assert.equal(statsData.leftoverSwept.toNumber(), 0);
+      assert.equal(statsData.shard, 0);
+    });
+
+    it("Only creates the configured number of stats shards", async () => {
+      const [outOfRange] = PublicKey.findProgramAddressSync(
+        [Buffer.from("stats"), Buffer.from([16])],
+        program.programId
+      );
+      try {
+        await program.methods
+          .initStats(16)
+          .accountsPartial({ payer: authority.publicKey, stats: outOfRange })
+          .signers([authority])
+          .rpc();
+        assert.fail("A shard past STATS_SHARDS should be rejected");
+      } catch (error) {
+        expect(error.message).to.include("InvalidConfigError");
+      }
+    });
+
+    const withdrawalPda = async () => {
+      const { withdrawalCount } = await program.account.matchConfig.fetch(config);
+      return PublicKey.findProgramAddressSync(
+        [Buffer.from("withdrawal"), withdrawalCount.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      )[0];
+    };
+
+    it("Rejects a withdrawal to a destination off the allowlist", async () => {
+      try {
+        await program.methods
+          .queueWithdrawal(new anchor.BN(1_000_000), playerA.publicKey)
+          .accountsPartial({ authority: authority.publicKey, config, withdrawal: await withdrawalPda() })
+          .signers([authority])
+          .rpc();
+        assert.fail("Queueing a withdrawal to an unknown destination should fail");
+      } catch (error) {
+        expect(error.message).to.include("InvalidDestinationError");
+      }
+    });
+
+    it("A new destination waits out the withdrawal delay", async () => {
+      await program.methods
+        .addWithdrawDestination(authority.publicKey)
+        .accountsPartial({ authority: authority.publicKey, config })
         .signers([authority])
         .rpc();
-      const finalTreasuryBalance = await connection.getBalance(treasuryPda);
-      const transferAmount = initialTreasuryBalance - finalTreasuryBalance;
-      const authorityFinalBalance = await connection.getBalance(authority.publicKey);
-      assert.ok(
-        Math.abs(authorityFinalBalance - (authorityInitialBalance + transferAmount)) < 10_000, // 10k lamports tolerance (~0.00001 SOL)
-        `Authority did not receive expected funds. Expected ≈${transferAmount}, got ${authorityFinalBalance}`
-      );
-      assert.ok(finalTreasuryBalance >= treasuryRent, "Treasury balance should decrease");
-    })
+      const configData = await program.account.matchConfig.fetch(config);
+      const [destination] = configData.withdrawDestinations;
+      assert.ok(destination.key.equals(authority.publicKey));
+      assert.isAtLeast(destination.activeAt.toNumber(), Math.floor(Date.now() / 1000) + configData.withdrawalDelay.toNumber() - 60);
+
+      try {
+        await program.methods
+          .queueWithdrawal(new anchor.BN(1_000_000), authority.publicKey)
+          .accountsPartial({ authority: authority.publicKey, config, withdrawal: await withdrawalPda() })
+          .signers([authority])
+          .rpc();
+        assert.fail("A destination can't be used before its delay has passed");
+      } catch (error) {
+        expect(error.message).to.include("InvalidDestinationError");
+      }
+    });
+
+    it("Withdrawal limits only loosen after the delay", async () => {
+      const initialConfig = await program.account.matchConfig.fetch(config);
+      const setWithdrawalParams = (delay: anchor.BN, cap: anchor.BN) =>
+        program.methods
+          .setWithdrawalParams(delay, initialConfig.withdrawalEpoch, cap)
+          .accountsPartial({ authority: authority.publicKey, config })
+          .signers([authority])
+          .rpc();
+
+      try {
+        await setWithdrawalParams(new anchor.BN(1), initialConfig.withdrawalEpochCap);
+        assert.fail("The delay can't go below the hard-coded minimum");
+      } catch (error) {
+        expect(error.message).to.include("InvalidConfigError");
+      }
+
+      await setWithdrawalParams(initialConfig.withdrawalDelay, initialConfig.withdrawalEpochCap.muln(2));
+      let configData = await program.account.matchConfig.fetch(config);
+      assert.ok(configData.withdrawalEpochCap.eq(initialConfig.withdrawalEpochCap), "A higher cap doesn't apply at once");
+      assert.ok(configData.pendingWithdrawalLimits.epochCap.eq(initialConfig.withdrawalEpochCap.muln(2)));
+
+      await setWithdrawalParams(initialConfig.withdrawalDelay, initialConfig.withdrawalEpochCap.divn(2));
+      configData = await program.account.matchConfig.fetch(config);
+      assert.ok(configData.withdrawalEpochCap.eq(initialConfig.withdrawalEpochCap.divn(2)), "A lower cap applies at once");
+      assert.isNull(configData.pendingWithdrawalLimits, "Tightening drops the pending loosening");
+    });
   })
 
   describe("A chess match with Draw Result", () => {
@@ -237,11 +345,13 @@ describe("capstone-chess-betting", () => {
     });
 
     it("Initialize Match for Draw", async () => {
-      matchDuration = 60;
+      timeControl = { baseSeconds: 60, incrementSeconds: 0, delaySeconds: 0 };
       const tx = await program.methods
-        .initializeMatch(seed, code, matchDuration, betAmount, null, null)
+        .initializeMatch(seed, code, timeControl, { standard: {} }, colorCommitment, betAmount, null, null, null)
         .accountsPartial({
+          stats,
           playerA: playerA.publicKey,
+          payer: playerA.publicKey,
           matchAccount: matchAccount,
           vault: vault,
           systemProgram: SystemProgram.programId,
@@ -251,7 +361,7 @@ describe("capstone-chess-betting", () => {
       const matchData = await program.account.matchState.fetch(matchAccount);
       const vaultAccount = await connection.getAccountInfo(vault);
       matchStatus = matchData.status;
-      assert.ok(matchData.matchDuration === matchDuration);
+      assert.ok(matchData.timeControl.baseSeconds === timeControl.baseSeconds);
       assert.ok(matchData.betAmount.eq(betAmount));
       assert.ok(matchData.playerA.equals(playerA.publicKey));
       assert.ok(matchData.playerB === null);
@@ -261,8 +371,9 @@ describe("capstone-chess-betting", () => {
 
     it("Accept Match for Draw", async () => {
       const tx = await program.methods
-        .acceptMatch(code)
+        .acceptMatch(code, { standard: {} }, playerBRandom, null)
         .accountsPartial({
+          stats,
           playerB: playerB.publicKey,
           playerA: playerA.publicKey,
           matchAccount: matchAccount,
@@ -282,14 +393,16 @@ describe("capstone-chess-betting", () => {
     it("Final Payouts for Draw", async () => {
       winner = null; // Simulating a draw
       const initialBalancePlayerA = await connection.getBalance(playerA.publicKey);
+      const matchRent = await connection.getBalance(matchAccount);
       const initialBalancePlayerB = await connection.getBalance(playerB.publicKey);
       const initialTreasuryBalance = await connection.getBalance(treasuryPda);
       const vaultAccount = await connection.getAccountInfo(vault);
       const vaultRent = await connection.getMinimumBalanceForRentExemption(vaultAccount.data.length);
 
       const tx = await program.methods
-        .finalPayouts(code, winner)
+        .finalPayouts(code, winner, { agreement: {} })
         .accountsPartial({
+          stats,
           authority: authority.publicKey,
           playerA: playerA.publicKey,
           playerB: playerB.publicKey,
@@ -333,8 +446,8 @@ describe("capstone-chess-betting", () => {
       // Fix asserts to match program logic
       assert.equal(
         finalBalancePlayerA,
-        initialBalancePlayerA + refundAmount.toNumber(),
-        "Player A should receive their bet minus 1%"
+        initialBalancePlayerA + refundAmount.toNumber() + matchRent,
+        "Player A should receive their bet minus 1% and the match rent"
       );
       assert.equal(
         finalBalancePlayerB,
@@ -389,23 +502,26 @@ describe("capstone-chess-betting", () => {
     });
 
     it("Initialize Match for Cancel", async () => {
-      matchDuration = 60;
+      timeControl = { baseSeconds: 60, incrementSeconds: 0, delaySeconds: 0 };
       const tx = await program.methods
-        .initializeMatch(seed, code, matchDuration, betAmount, null, null)
+        .initializeMatch(seed, code, timeControl, { standard: {} }, colorCommitment, betAmount, null, null, null)
         .accountsPartial({
+          stats,
           playerA: playerA.publicKey,
+          payer: authority.publicKey, // a relayer funds the record
           matchAccount: matchAccount,
           vault: vault,
           systemProgram: SystemProgram.programId,
         })
-        .signers([playerA])
+        .signers([playerA, authority])
         .rpc();
       const matchData = await program.account.matchState.fetch(matchAccount);
       const vaultAccount = await connection.getAccountInfo(vault);
       matchStatus = matchData.status;
-      assert.ok(matchData.matchDuration === matchDuration);
+      assert.ok(matchData.timeControl.baseSeconds === timeControl.baseSeconds);
       assert.ok(matchData.betAmount.eq(betAmount));
       assert.ok(matchData.playerA.equals(playerA.publicKey));
+      assert.ok(matchData.payer.equals(authority.publicKey));
       assert.ok(matchData.playerB === null);
       assert.ok("waiting" in matchStatus);
       assert.equal(vaultAccount.lamports, betAmount.toNumber(), "Vault should contain the total bet amount here");
@@ -413,11 +529,14 @@ describe("capstone-chess-betting", () => {
 
     it("Cancel Match", async () => {
       const initialBalancePlayerA = await connection.getBalance(playerA.publicKey);
+      const initialBalanceRelayer = await connection.getBalance(authority.publicKey);
+      const matchRent = await connection.getBalance(matchAccount);
       const initialBalancePlayerB = await connection.getBalance(playerB.publicKey);
 
       const tx = await program.methods
         .cancelMatch(code)
         .accountsPartial({
+          stats,
           playerA: playerA.publicKey,
           playerB: playerB.publicKey,
           config: config,
@@ -434,6 +553,11 @@ describe("capstone-chess-betting", () => {
       const finalBalancePlayerB = await connection.getBalance(playerB.publicKey);
 
       assert.equal(finalBalancePlayerA, initialBalancePlayerA + betAmount.toNumber(), "Player A should receive their bet back");
+      assert.equal(
+        await connection.getBalance(authority.publicKey),
+        initialBalanceRelayer + matchRent,
+        "The relayer that funded the match should get its rent back"
+      );
       assert.equal(finalBalancePlayerB, initialBalancePlayerB, "Player B should not receive any funds");
     });
 
@@ -469,11 +593,13 @@ describe("capstone-chess-betting", () => {
     });
 
     it("Initialize Match for Cancel in Progress", async () => {
-      matchDuration = 60;
+      timeControl = { baseSeconds: 60, incrementSeconds: 0, delaySeconds: 0 };
       const tx = await program.methods
-        .initializeMatch(seed, code, matchDuration, betAmount, null, null)
+        .initializeMatch(seed, code, timeControl, { standard: {} }, colorCommitment, betAmount, null, null, null)
         .accountsPartial({
+          stats,
           playerA: playerA.publicKey,
+          payer: playerA.publicKey,
           matchAccount: matchAccount,
           vault: vault,
           systemProgram: SystemProgram.programId,
@@ -483,7 +609,7 @@ describe("capstone-chess-betting", () => {
       const matchData = await program.account.matchState.fetch(matchAccount);
       const vaultAccount = await connection.getAccountInfo(vault);
       matchStatus = matchData.status;
-      assert.ok(matchData.matchDuration === matchDuration);
+      assert.ok(matchData.timeControl.baseSeconds === timeControl.baseSeconds);
       assert.ok(matchData.betAmount.eq(betAmount));
       assert.ok(matchData.playerA.equals(playerA.publicKey));
       assert.ok(matchData.playerB === null);
@@ -493,8 +619,9 @@ describe("capstone-chess-betting", () => {
 
     it("Accept Match for Cancel in Progress", async () => {
       const tx = await program.methods
-        .acceptMatch(code)
+        .acceptMatch(code, { standard: {} }, playerBRandom, null)
         .accountsPartial({
+          stats,
           playerB: playerB.publicKey,
           playerA: playerA.publicKey,
           matchAccount: matchAccount,
@@ -512,13 +639,58 @@ describe("capstone-chess-betting", () => {
       assert.ok(matchData.playerA.equals(playerA.publicKey));
     });
 
+    it("Rejects inconsistent cancel penalty params", async () => {
+      const setParams = (bps: number, step: number, stepBps: number, maxBps: number, opponentShareBps: number) =>
+        program.methods
+          .setCancelPenaltyParams(bps, new anchor.BN(step), stepBps, maxBps, opponentShareBps)
+          .accountsPartial({ feeManager: authority.publicKey, config, roleAssignment: null })
+          .signers([authority])
+          .rpc();
+      const invalid = [
+        { params: [600, 600, 100, 500, 7500], error: "InvalidFeeBps" }, // base above the cap
+        { params: [100, 600, 100, 10_001, 7500], error: "InvalidFeeBps" }, // cap above 100%
+        { params: [100, 600, 100, 500, 10_001], error: "InvalidFeeBps" }, // opponent share above 100%
+        { params: [100, 0, 100, 500, 7500], error: "InvalidConfigError" }, // no step to escalate by
+      ];
+      for (const { params, error: expected } of invalid) {
+        try {
+          await setParams(...(params as [number, number, number, number, number]));
+          assert.fail(`${params} should be rejected`);
+        } catch (error) {
+          expect(error.message).to.include(expected);
+        }
+      }
+
+      // Setting the defaults again leaves the config as the cancel below expects it
+      await setParams(100, 600, 100, 500, 7500);
+      const configData = await program.account.matchConfig.fetch(config);
+      assert.equal(configData.cancelPenaltyBps, 100);
+      assert.equal(configData.cancelPenaltyStep.toNumber(), 600);
+      assert.equal(configData.cancelPenaltyStepBps, 100);
+      assert.equal(configData.maxCancelPenaltyBps, 500);
+      assert.equal(configData.cancelOpponentShareBps, 7500);
+    });
+
     it("Cancel Match in Progress", async () => {
       const initialBalancePlayerA = await connection.getBalance(playerA.publicKey);
+      const matchRent = await connection.getBalance(matchAccount);
       const initialBalancePlayerB = await connection.getBalance(playerB.publicKey);
 
+      try {
+        await program.methods
+          .cancelMatch(code)
+          .accountsPartial({ stats, playerA: playerA.publicKey, playerB: authority.publicKey, config, player: playerA.publicKey, treasuryPda, matchAccount, vault })
+          .signers([playerA])
+          .rpc();
+        assert.fail("The compensation can't be sent to someone other than the opponent");
+      } catch (error) {
+        expect(error.message).to.include("InvalidPlayerError");
+      }
+
       const tx = await program.methods
         .cancelMatch(code)
         .accountsPartial({
+          stats,
           playerA: playerA.publicKey,
           playerB: playerB.publicKey,
           config: config,
@@ -534,8 +706,2348 @@ describe("capstone-chess-betting", () => {
       const finalBalancePlayerA = await connection.getBalance(playerA.publicKey);
       const finalBalancePlayerB = await connection.getBalance(playerB.publicKey);
 
-      assert.ok(finalBalancePlayerA < initialBalancePlayerA + betAmount.toNumber(), "Player A should receive their bet back - some fee deducted");
-      assert.ok(finalBalancePlayerB > initialBalancePlayerB, "Player B should receive their bet back");
+      // Cancelled within the first step: 1% of A's stake, three quarters of it to B
+      const penalty = betAmount.div(new anchor.BN(100));
+      const compensation = penalty.muln(3).divn(4);
+      assert.equal(finalBalancePlayerA, initialBalancePlayerA + betAmount.sub(penalty).toNumber() + matchRent, "Player A should receive their bet back minus the penalty, and the match rent");
+      assert.equal(finalBalancePlayerB, initialBalancePlayerB + betAmount.add(compensation).toNumber(), "Player B should receive their bet back plus part of A's penalty");
+    });
+  });
+
+  describe("A match with a spectator side pool", () => {
+    let sidePool: PublicKey;
+    let sideVault: PublicKey;
+    let spectatorA: anchor.web3.Keypair;
+    let spectatorB: anchor.web3.Keypair;
+    let sideBetA: PublicKey;
+    let sideBetB: PublicKey;
+    const sideBetAmount = new anchor.BN(LAMPORTS_PER_SOL / 2);
+
+    before(async () => {
+      seed = new anchor.BN(5);
+      betAmount = new anchor.BN(LAMPORTS_PER_SOL * 1);
+      playerA = Keypair.generate();
+      playerB = Keypair.generate();
+      spectatorA = Keypair.generate();
+      spectatorB = Keypair.generate();
+
+      for (const kp of [playerA, playerB, spectatorA, spectatorB]) {
+        const airdrop = await connection.requestAirdrop(kp.publicKey, LAMPORTS_PER_SOL * 5);
+        await connection.confirmTransaction(airdrop);
+      }
+      code = "sidepool123";
+
+      [matchAccount, bump] = PublicKey.findProgramAddressSync(
+        [Buffer.from("match"), seed.toArrayLike(Buffer, "le", 8), Buffer.from(code), playerA.publicKey.toBuffer()],
+        program.programId
+      );
+      [vault, vaultBump] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), matchAccount.toBuffer()],
+        program.programId
+      );
+      [sidePool] = PublicKey.findProgramAddressSync(
+        [Buffer.from("side_pool"), matchAccount.toBuffer()],
+        program.programId
+      );
+      [sideVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("side_vault"), sidePool.toBuffer()],
+        program.programId
+      );
+      [sideBetA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("side_bet"), sidePool.toBuffer(), spectatorA.publicKey.toBuffer()],
+        program.programId
+      );
+      [sideBetB] = PublicKey.findProgramAddressSync(
+        [Buffer.from("side_bet"), sidePool.toBuffer(), spectatorB.publicKey.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .initializeMatch(seed, code, { baseSeconds: 60, incrementSeconds: 0, delaySeconds: 0 }, { standard: {} }, colorCommitment, betAmount, null, null, null)
+        .accountsPartial({ stats, playerA: playerA.publicKey, payer: playerA.publicKey, matchAccount, vault })
+        .signers([playerA])
+        .rpc();
+      await program.methods
+        .acceptMatch(code, { standard: {} }, playerBRandom, null)
+        .accountsPartial({ stats, playerB: playerB.publicKey, playerA: playerA.publicKey, matchAccount, vault })
+        .signers([playerB])
+        .rpc();
+    });
+
+    it("Open Side Pool", async () => {
+      await program.methods
+        .openSidePool(code)
+        .accountsPartial({ payer: spectatorA.publicKey, matchAccount, sidePool, sideVault, config })
+        .signers([spectatorA])
+        .rpc();
+      const poolData = await program.account.sidePool.fetch(sidePool);
+      const matchData = await program.account.matchState.fetch(matchAccount);
+      assert.ok("open" in poolData.status);
+      assert.ok(poolData.matchAccount.equals(matchAccount));
+      assert.ok(matchData.hasSidePool);
+    });
+
+    it("Place Side Bets", async () => {
+      await program.methods
+        .placeSideBet({ playerA: {} }, sideBetAmount)
+        .accountsPartial({ bettor: spectatorA.publicKey, matchAccount, sidePool, sideBet: sideBetA, sideVault })
+        .signers([spectatorA])
+        .rpc();
+      await program.methods
+        .placeSideBet({ playerB: {} }, sideBetAmount)
+        .accountsPartial({ bettor: spectatorB.publicKey, matchAccount, sidePool, sideBet: sideBetB, sideVault })
+        .signers([spectatorB])
+        .rpc();
+      const poolData = await program.account.sidePool.fetch(sidePool);
+      assert.ok(poolData.totals[0].eq(sideBetAmount));
+      assert.ok(poolData.totals[1].eq(sideBetAmount));
+    });
+
+    it("Players can't side bet", async () => {
+      const [playerSideBet] = PublicKey.findProgramAddressSync(
+        [Buffer.from("side_bet"), sidePool.toBuffer(), playerA.publicKey.toBuffer()],
+        program.programId
+      );
+      try {
+        await program.methods
+          .placeSideBet({ playerA: {} }, sideBetAmount)
+          .accountsPartial({ bettor: playerA.publicKey, matchAccount, sidePool, sideBet: playerSideBet, sideVault })
+          .signers([playerA])
+          .rpc();
+        assert.fail("Player A should not be able to side bet");
+      } catch (error) {
+        expect(error.message).to.include("PlayerSideBetError");
+      }
+    });
+
+    it("No side bets once a player has reported the result", async () => {
+      await program.methods
+        .reportResult(code, { outcome: { playerA: {} }, reason: { resignation: {} } })
+        .accountsPartial({
+          stats,
+          reporter: playerA.publicKey,
+          playerA: playerA.publicKey,
+          playerB: playerB.publicKey,
+          matchAccount,
+          vault,
+          config,
+          treasuryPda,
+          sidePool,
+          sideVault,
+        })
+        .signers([playerA])
+        .rpc();
+      const [lateBet] = PublicKey.findProgramAddressSync(
+        [Buffer.from("side_bet"), sidePool.toBuffer(), authority.publicKey.toBuffer()],
+        program.programId
+      );
+      try {
+        await program.methods
+          .placeSideBet({ playerA: {} }, sideBetAmount)
+          .accountsPartial({ bettor: authority.publicKey, matchAccount, sidePool, sideBet: lateBet, sideVault })
+          .signers([authority])
+          .rpc();
+        assert.fail("A bet after a reported result should fail");
+      } catch (error) {
+        expect(error.message).to.include("SidePoolClosed");
+      }
+    });
+
+    it("Final Payouts resolves the Side Pool", async () => {
+      const initialTreasuryBalance = await connection.getBalance(treasuryPda);
+      const initialSidePoolFees = (await program.account.protocolStats.fetch(stats)).sidePoolFees;
+      await program.methods
+        .finalPayouts(code, playerA.publicKey, { resignation: {} })
+        .accountsPartial({
+          stats,
+          authority: authority.publicKey,
+          playerA: playerA.publicKey,
+          playerB: playerB.publicKey,
+          matchAccount,
+          vault,
+          treasuryPda,
+          config,
+          sidePool,
+          sideVault,
+        })
+        .signers([authority])
+        .rpc();
+      const poolData = await program.account.sidePool.fetch(sidePool);
+      const sideFee = sideBetAmount.mul(new anchor.BN(2)).div(new anchor.BN(50)); // 2% of the side pool
+      assert.ok("resolved" in poolData.status);
+      assert.ok("playerA" in poolData.result);
+      assert.ok(poolData.payoutPool.eq(sideBetAmount.mul(new anchor.BN(2)).sub(sideFee)));
+      assert.ok((await connection.getBalance(treasuryPda)) >= initialTreasuryBalance + sideFee.toNumber());
+      const statsData = await program.account.protocolStats.fetch(stats);
+      assert.ok(statsData.sidePoolFees.sub(initialSidePoolFees).eq(sideFee));
+    });
+
+    it("Claim Side Bets", async () => {
+      const poolData = await program.account.sidePool.fetch(sidePool);
+      const initialBalanceA = await connection.getBalance(spectatorA.publicKey);
+      const initialBalanceB = await connection.getBalance(spectatorB.publicKey);
+      const betRent = (await connection.getAccountInfo(sideBetA)).lamports;
+
+      await program.methods
+        .claimSideBet()
+        .accountsPartial({ bettor: spectatorA.publicKey, sidePool, sideBet: sideBetA, sideVault })
+        .signers([spectatorA])
+        .rpc();
+      await program.methods
+        .claimSideBet()
+        .accountsPartial({ bettor: spectatorB.publicKey, sidePool, sideBet: sideBetB, sideVault })
+        .signers([spectatorB])
+        .rpc();
+
+      const finalBalanceA = await connection.getBalance(spectatorA.publicKey);
+      const finalBalanceB = await connection.getBalance(spectatorB.publicKey);
+      assert.equal(finalBalanceA, initialBalanceA + poolData.payoutPool.toNumber() + betRent, "Winning spectator takes the whole net pool");
+      assert.equal(finalBalanceB, initialBalanceB + betRent, "Losing spectator only gets the bet account rent back");
+    });
+
+    it("Close Side Pool returns the pool and vault rent to its opener", async () => {
+      const initialBalance = await connection.getBalance(spectatorA.publicKey);
+      const poolRent = (await connection.getAccountInfo(sidePool)).lamports;
+      const vaultBalance = await connection.getBalance(sideVault);
+
+      await program.methods
+        .closeSidePool()
+        .accountsPartial({ cranker: spectatorB.publicKey, sidePool, sideVault, payer: spectatorA.publicKey })
+        .signers([spectatorB])
+        .rpc();
+
+      assert.equal(await connection.getBalance(spectatorA.publicKey), initialBalance + poolRent + vaultBalance);
+      assert.equal(await connection.getBalance(sideVault), 0);
+      assert.isNull(await connection.getAccountInfo(sidePool));
+    });
+  });
+
+  describe("Matchmaking queue", () => {
+    let ticketA: PublicKey;
+    let ticketB: PublicKey;
+    let ticketVaultA: PublicKey;
+    let ticketVaultB: PublicKey;
+    const queueTimeControl = { baseSeconds: 300, incrementSeconds: 3, delaySeconds: 0 };
+    const playerBSecret = randomBytes(32);
+    const playerBCommitment = Array.from(createHash("sha256").update(playerBSecret).digest());
+
+    const findTicket = (player: PublicKey, bet: anchor.BN, tc: typeof queueTimeControl) => {
+      const timeControlBuffer = Buffer.alloc(12);
+      timeControlBuffer.writeUInt32LE(tc.baseSeconds, 0);
+      timeControlBuffer.writeUInt32LE(tc.incrementSeconds, 4);
+      timeControlBuffer.writeUInt32LE(tc.delaySeconds, 8);
+      const [ticket] = PublicKey.findProgramAddressSync(
+        [Buffer.from("ticket"), player.toBuffer(), bet.toArrayLike(Buffer, "le", 8), timeControlBuffer],
+        program.programId
+      );
+      const [ticketVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("ticket_vault"), ticket.toBuffer()],
+        program.programId
+      );
+      return [ticket, ticketVault];
+    };
+
+    before(async () => {
+      seed = new anchor.BN(6);
+      betAmount = new anchor.BN(LAMPORTS_PER_SOL * 1);
+      playerA = Keypair.generate();
+      playerB = Keypair.generate();
+
+      let airdrop1 = await connection.requestAirdrop(playerA.publicKey, LAMPORTS_PER_SOL * 5);
+      let airdrop2 = await connection.requestAirdrop(playerB.publicKey, LAMPORTS_PER_SOL * 5);
+      await connection.confirmTransaction(airdrop1);
+      await connection.confirmTransaction(airdrop2);
+
+      [ticketA, ticketVaultA] = findTicket(playerA.publicKey, betAmount, queueTimeControl);
+      [ticketB, ticketVaultB] = findTicket(playerB.publicKey, betAmount, queueTimeControl);
+
+      // Queued matches are created with an empty code
+      [matchAccount, bump] = PublicKey.findProgramAddressSync(
+        [Buffer.from("match"), seed.toArrayLike(Buffer, "le", 8), Buffer.from(""), playerA.publicKey.toBuffer()],
+        program.programId
+      );
+      [vault, vaultBump] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), matchAccount.toBuffer()],
+        program.programId
+      );
+    });
+
+    it("Enqueue and Dequeue", async () => {
+      const initialBalance = await connection.getBalance(playerA.publicKey);
+      await program.methods
+        .enqueue(betAmount, queueTimeControl, colorCommitment)
+        .accountsPartial({ player: playerA.publicKey, ticket: ticketA, ticketVault: ticketVaultA })
+        .signers([playerA])
+        .rpc();
+      assert.equal(await connection.getBalance(ticketVaultA), betAmount.toNumber(), "Ticket vault should hold the stake");
+
+      await program.methods
+        .dequeue()
+        .accountsPartial({ player: playerA.publicKey, ticket: ticketA, ticketVault: ticketVaultA })
+        .signers([playerA])
+        .rpc();
+      assert.equal(await connection.getBalance(playerA.publicKey), initialBalance, "Stake and rent should be refunded");
+      assert.equal(await connection.getAccountInfo(ticketA), null, "Ticket should be closed");
+    });
+
+    it("Pair two tickets into a match", async () => {
+      for (const [player, ticket, ticketVault, commitment] of [
+        [playerA, ticketA, ticketVaultA, colorCommitment],
+        [playerB, ticketB, ticketVaultB, playerBCommitment],
+      ] as [Keypair, PublicKey, PublicKey, number[]][]) {
+        await program.methods
+          .enqueue(betAmount, queueTimeControl, commitment)
+          .accountsPartial({ player: player.publicKey, ticket, ticketVault })
+          .signers([player])
+          .rpc();
+      }
+
+      const pair = () =>
+        program.methods
+          .pair(seed)
+          .accountsPartial({
+            stats,
+            cranker: wallet.publicKey,
+            playerA: playerA.publicKey,
+            playerB: playerB.publicKey,
+            ticketA,
+            ticketVaultA,
+            ticketB,
+            ticketVaultB,
+            matchAccount,
+            vault,
+            config,
+          })
+          .rpc();
+      const setPaused = (paused: boolean) =>
+        program.methods
+          .setPaused(paused)
+          .accountsPartial({ authority: authority.publicKey, config, roleAssignment: null })
+          .signers([authority])
+          .rpc();
+
+      await setPaused(true);
+      try {
+        await pair();
+        assert.fail("Pairing while paused should fail");
+      } catch (error) {
+        expect(error.message).to.include("PausedError");
+      }
+      await setPaused(false);
+      await pair();
+
+      const matchData = await program.account.matchState.fetch(matchAccount);
+      assert.ok("inProgress" in matchData.status);
+      assert.ok(matchData.playerA.equals(playerA.publicKey));
+      assert.ok(matchData.playerB.equals(playerB.publicKey));
+      assert.equal(matchData.timeControl.baseSeconds, queueTimeControl.baseSeconds);
+      assert.equal(matchData.timeControl.incrementSeconds, queueTimeControl.incrementSeconds);
+      assert.equal(await connection.getBalance(vault), 2 * betAmount.toNumber(), "Vault should hold both stakes");
+      assert.equal(await connection.getAccountInfo(ticketA), null, "Ticket A should be closed");
+      assert.equal(await connection.getAccountInfo(ticketB), null, "Ticket B should be closed");
+    });
+
+    it("Paired players reveal their colour secrets, player B first", async () => {
+      let matchData = await program.account.matchState.fetch(matchAccount);
+      assert.isNull(matchData.white, "Colours can't be known before both secrets are revealed");
+
+      try {
+        await program.methods
+          .revealColors("", Array.from(colorSecret))
+          .accountsPartial({ player: playerA.publicKey, matchAccount })
+          .signers([playerA])
+          .rpc();
+        assert.fail("Player A can't reveal before player B on a paired match");
+      } catch (error) {
+        expect(error.message).to.include("InvalidPlayerError");
+      }
+
+      await program.methods
+        .revealColors("", Array.from(playerBSecret))
+        .accountsPartial({ player: playerB.publicKey, matchAccount })
+        .signers([playerB])
+        .rpc();
+      await program.methods
+        .revealColors("", Array.from(colorSecret))
+        .accountsPartial({ player: playerA.publicKey, matchAccount })
+        .signers([playerA])
+        .rpc();
+
+      matchData = await program.account.matchState.fetch(matchAccount);
+      const coin = createHash("sha256").update(colorSecret).update(playerBSecret).digest()[0] & 1;
+      assert.ok(matchData.white.equals(coin == 0 ? playerA.publicKey : playerB.publicKey));
+    });
+  });
+
+  describe("Time control bounds", () => {
+    it("Rejects a time control outside the configured bounds", async () => {
+      seed = new anchor.BN(7);
+      code = "badclock123";
+      playerA = Keypair.generate();
+      const airdrop = await connection.requestAirdrop(playerA.publicKey, LAMPORTS_PER_SOL * 2);
+      await connection.confirmTransaction(airdrop);
+      [matchAccount] = PublicKey.findProgramAddressSync(
+        [Buffer.from("match"), seed.toArrayLike(Buffer, "le", 8), Buffer.from(code), playerA.publicKey.toBuffer()],
+        program.programId
+      );
+      [vault] = PublicKey.findProgramAddressSync([Buffer.from("vault"), matchAccount.toBuffer()], program.programId);
+
+      try {
+        await program.methods
+          .initializeMatch(seed, code, { baseSeconds: 1, incrementSeconds: 0, delaySeconds: 0 }, { standard: {} }, colorCommitment, new anchor.BN(LAMPORTS_PER_SOL), null, null, null)
+          .accountsPartial({ stats, playerA: playerA.publicKey, payer: playerA.publicKey, matchAccount, vault, config })
+          .signers([playerA])
+          .rpc();
+        assert.fail("A 1 second game should be rejected");
+      } catch (error) {
+        expect(error.message).to.include("InvalidTimeControlError");
+      }
+    });
+
+    it("Rejects a bet outside the configured limits", async () => {
+      const tooSmall = new anchor.BN(1_000);
+      try {
+        await program.methods
+          .initializeMatch(seed, code, { baseSeconds: 300, incrementSeconds: 0, delaySeconds: 0 }, { standard: {} }, colorCommitment, tooSmall, null, null, null)
+          .accountsPartial({ stats, playerA: playerA.publicKey, payer: playerA.publicKey, matchAccount, vault, config })
+          .signers([playerA])
+          .rpc();
+        assert.fail("A bet below the minimum should be rejected");
+      } catch (error) {
+        expect(error.message).to.include("BetOutOfRangeError");
+      }
+
+      try {
+        await program.methods
+          .setBetLimits(new anchor.BN(1_000), new anchor.BN(LAMPORTS_PER_SOL))
+          .accountsPartial({ authority: authority.publicKey, config })
+          .signers([authority])
+          .rpc();
+        assert.fail("A minimum below the vault's rent should be rejected");
+      } catch (error) {
+        expect(error.message).to.include("InvalidConfigError");
+      }
+    });
+  });
+
+  describe("A Chess960 match", () => {
+    before(async () => {
+      seed = new anchor.BN(8);
+      betAmount = new anchor.BN(LAMPORTS_PER_SOL * 1);
+      playerA = Keypair.generate();
+      playerB = Keypair.generate();
+
+      let airdrop1 = await connection.requestAirdrop(playerA.publicKey, LAMPORTS_PER_SOL * 5);
+      let airdrop2 = await connection.requestAirdrop(playerB.publicKey, LAMPORTS_PER_SOL * 5);
+      await connection.confirmTransaction(airdrop1);
+      await connection.confirmTransaction(airdrop2);
+      code = "chess960";
+
+      [matchAccount, bump] = PublicKey.findProgramAddressSync(
+        [Buffer.from("match"), seed.toArrayLike(Buffer, "le", 8), Buffer.from(code), playerA.publicKey.toBuffer()],
+        program.programId
+      );
+      [vault, vaultBump] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), matchAccount.toBuffer()],
+        program.programId
+      );
+
+      timeControl = { baseSeconds: 180, incrementSeconds: 2, delaySeconds: 0 };
+      await program.methods
+        .initializeMatch(seed, code, timeControl, { chess960: {} }, colorCommitment, betAmount, null, null, null)
+        .accountsPartial({ stats, playerA: playerA.publicKey, payer: playerA.publicKey, matchAccount, vault })
+        .signers([playerA])
+        .rpc();
+    });
+
+    it("Rejects an acceptor expecting another variant", async () => {
+      try {
+        await program.methods
+          .acceptMatch(code, { standard: {} }, playerBRandom, null)
+          .accountsPartial({ stats, playerB: playerB.publicKey, playerA: playerA.publicKey, matchAccount, vault })
+          .signers([playerB])
+          .rpc();
+        assert.fail("Accepting with the wrong variant should fail");
+      } catch (error) {
+        expect(error.message).to.include("VariantMismatchError");
+      }
+    });
+
+    it("Draws the starting position from both players' secrets on reveal", async () => {
+      await program.methods
+        .acceptMatch(code, { chess960: {} }, playerBRandom, null)
+        .accountsPartial({ stats, playerB: playerB.publicKey, playerA: playerA.publicKey, matchAccount, vault })
+        .signers([playerB])
+        .rpc();
+      let matchData = await program.account.matchState.fetch(matchAccount);
+      assert.ok("chess960" in matchData.variant);
+      assert.isNull(matchData.startPosition, "Nobody can know the position before player A reveals");
+
+      await program.methods
+        .revealColors(code, Array.from(colorSecret))
+        .accountsPartial({ player: playerA.publicKey, matchAccount })
+        .signers([playerA])
+        .rpc();
+      matchData = await program.account.matchState.fetch(matchAccount);
+
+      const seed = createHash("sha256").update(colorSecret).update(Buffer.from(playerBRandom)).digest();
+      assert.equal(matchData.startPosition, Number(seed.readBigUInt64LE(8) % BigInt(960)));
+    });
+  });
+
+  describe("Player A never reveals colours", () => {
+    before(async () => {
+      seed = new anchor.BN(9);
+      betAmount = new anchor.BN(LAMPORTS_PER_SOL * 1);
+      playerA = Keypair.generate();
+      playerB = Keypair.generate();
+
+      let airdrop1 = await connection.requestAirdrop(playerA.publicKey, LAMPORTS_PER_SOL * 5);
+      let airdrop2 = await connection.requestAirdrop(playerB.publicKey, LAMPORTS_PER_SOL * 5);
+      await connection.confirmTransaction(airdrop1);
+      await connection.confirmTransaction(airdrop2);
+      code = "noreveal";
+
+      [matchAccount, bump] = PublicKey.findProgramAddressSync(
+        [Buffer.from("match"), seed.toArrayLike(Buffer, "le", 8), Buffer.from(code), playerA.publicKey.toBuffer()],
+        program.programId
+      );
+      [vault, vaultBump] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), matchAccount.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .setRevealWindow(new anchor.BN(1))
+        .accountsPartial({ authority: authority.publicKey, config })
+        .signers([authority])
+        .rpc();
+
+      timeControl = { baseSeconds: 60, incrementSeconds: 0, delaySeconds: 0 };
+      await program.methods
+        .initializeMatch(seed, code, timeControl, { standard: {} }, colorCommitment, betAmount, null, null, null)
+        .accountsPartial({ stats, playerA: playerA.publicKey, payer: playerA.publicKey, matchAccount, vault })
+        .signers([playerA])
+        .rpc();
+      await program.methods
+        .acceptMatch(code, { standard: {} }, playerBRandom, null)
+        .accountsPartial({ stats, playerB: playerB.publicKey, playerA: playerA.publicKey, matchAccount, vault })
+        .signers([playerB])
+        .rpc();
+    });
+
+    after(async () => {
+      await program.methods
+        .setRevealWindow(new anchor.BN(600))
+        .accountsPartial({ authority: authority.publicKey, config })
+        .signers([authority])
+        .rpc();
+    });
+
+    it("Player B claims the win", async () => {
+      await new Promise((resolve) => setTimeout(resolve, 3000));
+      const initialBalancePlayerB = await connection.getBalance(playerB.publicKey);
+
+      await program.methods
+        .claimRevealTimeout(code)
+        .accountsPartial({
+          stats,
+          claimant: playerB.publicKey,
+          playerA: playerA.publicKey,
+          playerB: playerB.publicKey,
+          matchAccount,
+          vault,
+          config,
+          treasuryPda,
+        })
+        .signers([playerB])
+        .rpc();
+
+      const totalBetAmount = betAmount.mul(new anchor.BN(2));
+      const winningAmount = totalBetAmount.sub(totalBetAmount.div(new anchor.BN(200))); // 0.5% fee deducted
+      assert.equal(
+        await connection.getBalance(playerB.publicKey),
+        initialBalancePlayerB + winningAmount.toNumber(),
+        "Player B should be paid as the winner"
+      );
+      assert.equal(await connection.getAccountInfo(matchAccount), null, "Match should be closed");
+    });
+  });
+
+  describe("A game played move by move on chain", () => {
+    let gameLog: PublicKey;
+
+    // from in bits 0-5, to in bits 6-11, squares numbered a1 = 0 .. h8 = 63
+    const packMove = (uci: string) => {
+      const square = (name: string) => (name.charCodeAt(0) - 97) + 8 * (Number(name[1]) - 1);
+      return square(uci.slice(0, 2)) | (square(uci.slice(2, 4)) << 6);
+    };
+
+    before(async () => {
+      seed = new anchor.BN(10);
+      betAmount = new anchor.BN(LAMPORTS_PER_SOL * 1);
+      playerA = Keypair.generate();
+      playerB = Keypair.generate();
+
+      let airdrop1 = await connection.requestAirdrop(playerA.publicKey, LAMPORTS_PER_SOL * 5);
+      let airdrop2 = await connection.requestAirdrop(playerB.publicKey, LAMPORTS_PER_SOL * 5);
+      await connection.confirmTransaction(airdrop1);
+      await connection.confirmTransaction(airdrop2);
+      code = "onchain";
+
+      [matchAccount, bump] = PublicKey.findProgramAddressSync(
+        [Buffer.from("match"), seed.toArrayLike(Buffer, "le", 8), Buffer.from(code), playerA.publicKey.toBuffer()],
+        program.programId
+      );
+      [vault, vaultBump] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), matchAccount.toBuffer()],
+        program.programId
+      );
+      [gameLog] = PublicKey.findProgramAddressSync(
+        [Buffer.from("game_log"), matchAccount.toBuffer()],
+        program.programId
+      );
+
+      timeControl = { baseSeconds: 300, incrementSeconds: 0, delaySeconds: 0 };
+      await program.methods
+        .initializeMatch(seed, code, timeControl, { standard: {} }, colorCommitment, betAmount, null, null, null)
+        .accountsPartial({ stats, playerA: playerA.publicKey, payer: playerA.publicKey, matchAccount, vault })
+        .signers([playerA])
+        .rpc();
+      await program.methods
+        .acceptMatch(code, { standard: {} }, playerBRandom, null)
+        .accountsPartial({ stats, playerB: playerB.publicKey, playerA: playerA.publicKey, matchAccount, vault })
+        .signers([playerB])
+        .rpc();
+      await program.methods
+        .revealColors(code, Array.from(colorSecret))
+        .accountsPartial({ player: playerA.publicKey, matchAccount })
+        .signers([playerA])
+        .rpc();
+    });
+
+    it("Start Game", async () => {
+      await program.methods
+        .startGame(code)
+        .accountsPartial({ player: playerA.publicKey, matchAccount, gameLog })
+        .signers([playerA])
+        .rpc();
+      const logData = await program.account.gameLog.fetch(gameLog);
+      const matchData = await program.account.matchState.fetch(matchAccount);
+      assert.ok(logData.matchAccount.equals(matchAccount));
+      assert.equal(logData.moves.length, 0);
+      assert.ok("ongoing" in logData.status);
+      assert.ok(matchData.hasGameLog);
+    });
+
+    it("Fool's mate ends the game and settles it", async () => {
+      const matchData = await program.account.matchState.fetch(matchAccount);
+      const white = matchData.white.equals(playerA.publicKey) ? playerA : playerB;
+      const black = white === playerA ? playerB : playerA;
+
+      try {
+        await program.methods
+          .submitMove(code, packMove("e7e5"))
+          .accountsPartial({ player: black.publicKey, matchAccount, gameLog })
+          .signers([black])
+          .rpc();
+        assert.fail("Black can't move first");
+      } catch (error) {
+        expect(error.message).to.include("NotYourTurnError");
+      }
+
+      for (const [player, uci] of [
+        [white, "f2f3"],
+        [black, "e7e5"],
+        [white, "g2g4"],
+        [black, "d8h4"],
+      ] as [Keypair, string][]) {
+        await program.methods
+          .submitMove(code, packMove(uci))
+          .accountsPartial({ player: player.publicKey, matchAccount, gameLog })
+          .signers([player])
+          .rpc();
+      }
+
+      const logData = await program.account.gameLog.fetch(gameLog);
+      assert.equal(logData.moves.length, 4);
+      assert.ok("checkmate" in logData.status);
+      assert.ok(logData.winner.equals(black.publicKey));
+
+      // the mated player can't walk away with their stake
+      for (const [withLog, expected] of [
+        [gameLog, "GameFinishedError"],
+        [null, "InvalidGameLogError"],
+      ] as [PublicKey | null, string][]) {
+        try {
+          await program.methods
+            .cancelMatch(code)
+            .accountsPartial({ stats, player: white.publicKey, playerA: playerA.publicKey, playerB: playerB.publicKey, matchAccount, vault, gameLog: withLog, config, treasuryPda })
+            .signers([white])
+            .rpc();
+          assert.fail("Cancelling a mated game should fail");
+        } catch (error) {
+          expect(error.message).to.include(expected);
+        }
+      }
+
+      // nor can a proposal hand the game to the mated player, it could never be finalized
+      const [proposal] = PublicKey.findProgramAddressSync([Buffer.from("proposal"), matchAccount.toBuffer()], program.programId);
+      const [bondVault] = PublicKey.findProgramAddressSync([Buffer.from("bond_vault"), proposal.toBuffer()], program.programId);
+      try {
+        await program.methods
+          .proposeResult(code, white.publicKey, { checkmate: {} })
+          .accountsPartial({ proposer: authority.publicKey, matchAccount, proposal, bondVault, gameLog, config })
+          .signers([authority])
+          .rpc();
+        assert.fail("A proposal against the board should fail");
+      } catch (error) {
+        expect(error.message).to.include("GameLogResultMismatchError");
+      }
+
+      const initialBalanceBlack = await connection.getBalance(black.publicKey);
+      await program.methods
+        .settleGame(code)
+        .accountsPartial({
+          stats,
+          cranker: wallet.publicKey,
+          playerA: playerA.publicKey,
+          playerB: playerB.publicKey,
+          matchAccount,
+          vault,
+          gameLog,
+          config,
+          treasuryPda,
+        })
+        .rpc();
+
+      const totalBetAmount = betAmount.mul(new anchor.BN(2));
+      const winningAmount = totalBetAmount.sub(totalBetAmount.div(new anchor.BN(200)));
+      assert.equal(await connection.getBalance(black.publicKey), initialBalanceBlack + winningAmount.toNumber());
+    });
+
+    it("Close Game Log", async () => {
+      await program.methods
+        .closeGameLog()
+        .accountsPartial({ payer: playerA.publicKey, matchAccount, gameLog })
+        .signers([playerA])
+        .rpc();
+      assert.equal(await connection.getAccountInfo(gameLog), null, "Game log should be closed");
+    });
+  });
+
+  describe("Compute budget of submit_move", () => {
+    let gameLog: PublicKey;
+
+    // a move has to fit the default per-instruction limit, clients don't request extra compute units
+    const SUBMIT_MOVE_CU_BUDGET = 200_000;
+
+    const packMove = (uci: string) => {
+      const square = (name: string) => (name.charCodeAt(0) - 97) + 8 * (Number(name[1]) - 1);
+      return square(uci.slice(0, 2)) | (square(uci.slice(2, 4)) << 6);
+    };
+
+    // Knight moves only, never repeating a position, so nothing resets or ends the repetition window
+    // before the fifty-move rule: the last move scans the largest history a game log can hold.
+    // Each side's knights stay off the squares that would check the other king.
+    const knightWalk = (plies: number): string[] => {
+      const name = (sq: number) => String.fromCharCode(97 + (sq % 8)) + (Math.floor(sq / 8) + 1);
+      const parse = (n: string) => (n.charCodeAt(0) - 97) + 8 * (Number(n[1]) - 1);
+      const jumps = [[1, 2], [2, 1], [2, -1], [1, -2], [-1, -2], [-2, -1], [-2, 1], [-1, 2]];
+      const checking = [["d6", "f6"].map(parse), ["d3", "f3"].map(parse)];
+      const knights = [["b1", "g1"].map(parse), ["b8", "g8"].map(parse)];
+      const others = new Set<number>();
+      for (let sq = 0; sq < 64; sq++) {
+        const rank = Math.floor(sq / 8);
+        if ((rank <= 1 || rank >= 6) && !knights[0].includes(sq) && !knights[1].includes(sq)) others.add(sq);
+      }
+      const key = (side: number) => `${[...knights[0]].sort()}|${[...knights[1]].sort()}|${side}`;
+      const seen = new Set<string>([key(0)]);
+      const path: string[] = [];
+
+      const walk = (side: number): boolean => {
+        if (path.length === plies) return true;
+        for (let k = 0; k < 2; k++) {
+          const from = knights[side][k];
+          for (const [df, dr] of jumps) {
+            const file = (from % 8) + df;
+            const rank = Math.floor(from / 8) + dr;
+            if (file < 0 || file > 7 || rank < 0 || rank > 7) continue;
+            const to = file + 8 * rank;
+            if (others.has(to) || knights[0].includes(to) || knights[1].includes(to) || checking[side].includes(to)) continue;
+
+            knights[side][k] = to;
+            const next = key(1 - side);
+            if (!seen.has(next)) {
+              seen.add(next);
+              path.push(name(from) + name(to));
+              if (walk(1 - side)) return true;
+              path.pop();
+              seen.delete(next);
+            }
+            knights[side][k] = from;
+          }
+        }
+        return false;
+      };
+
+      assert.ok(walk(0), "a non-repeating knight walk exists");
+      return path;
+    };
+
+    before(async () => {
+      seed = new anchor.BN(21);
+      betAmount = new anchor.BN(LAMPORTS_PER_SOL * 1);
+      playerA = Keypair.generate();
+      playerB = Keypair.generate();
+
+      let airdrop1 = await connection.requestAirdrop(playerA.publicKey, LAMPORTS_PER_SOL * 5);
+      let airdrop2 = await connection.requestAirdrop(playerB.publicKey, LAMPORTS_PER_SOL * 5);
+      await connection.confirmTransaction(airdrop1);
+      await connection.confirmTransaction(airdrop2);
+      code = "cubudget";
+
+      [matchAccount] = PublicKey.findProgramAddressSync(
+        [Buffer.from("match"), seed.toArrayLike(Buffer, "le", 8), Buffer.from(code), playerA.publicKey.toBuffer()],
+        program.programId
+      );
+      [vault] = PublicKey.findProgramAddressSync([Buffer.from("vault"), matchAccount.toBuffer()], program.programId);
+      [gameLog] = PublicKey.findProgramAddressSync([Buffer.from("game_log"), matchAccount.toBuffer()], program.programId);
+
+      timeControl = { baseSeconds: 3 * 60 * 60, incrementSeconds: 0, delaySeconds: 0 };
+      await program.methods
+        .initializeMatch(seed, code, timeControl, { standard: {} }, colorCommitment, betAmount, null, null, null)
+        .accountsPartial({ stats, playerA: playerA.publicKey, payer: playerA.publicKey, matchAccount, vault })
+        .signers([playerA])
+        .rpc();
+      await program.methods
+        .acceptMatch(code, { standard: {} }, playerBRandom, null)
+        .accountsPartial({ stats, playerB: playerB.publicKey, playerA: playerA.publicKey, matchAccount, vault })
+        .signers([playerB])
+        .rpc();
+      await program.methods
+        .revealColors(code, Array.from(colorSecret))
+        .accountsPartial({ player: playerA.publicKey, matchAccount })
+        .signers([playerA])
+        .rpc();
+      await program.methods
+        .startGame(code)
+        .accountsPartial({ player: playerA.publicKey, matchAccount, gameLog })
+        .signers([playerA])
+        .rpc();
+    });
+
+    it("Keeps every move within the budget up to a full repetition window", async () => {
+      const matchData = await program.account.matchState.fetch(matchAccount);
+      const white = matchData.white.equals(playerA.publicKey) ? playerA : playerB;
+      const black = white === playerA ? playerB : playerA;
+
+      const used: number[] = [];
+      const moves = knightWalk(100);
+      for (const [i, uci] of moves.entries()) {
+        const player = i % 2 == 0 ? white : black;
+        const signature = await program.methods
+          .submitMove(code, packMove(uci))
+          .accountsPartial({ player: player.publicKey, matchAccount, gameLog })
+          .signers([player])
+          .rpc({ commitment: "confirmed" });
+        const tx = await connection.getTransaction(signature, { commitment: "confirmed", maxSupportedTransactionVersion: 0 });
+        used.push(tx.meta.computeUnitsConsumed);
+      }
+
+      const logData = await program.account.gameLog.fetch(gameLog);
+      assert.equal(logData.positionHashes.length, 101, "the last move checked the whole repetition window");
+      assert.ok("fiftyMoveRule" in logData.status);
+
+      assert.ok(Math.max(...used) < SUBMIT_MOVE_CU_BUDGET, `a move used ${Math.max(...used)} compute units`);
+    });
+  });
+
+  describe("A player flags on the on-chain clock", () => {
+    let gameLog: PublicKey;
+
+    before(async () => {
+      seed = new anchor.BN(11);
+      betAmount = new anchor.BN(LAMPORTS_PER_SOL * 1);
+      playerA = Keypair.generate();
+      playerB = Keypair.generate();
+
+      let airdrop1 = await connection.requestAirdrop(playerA.publicKey, LAMPORTS_PER_SOL * 5);
+      let airdrop2 = await connection.requestAirdrop(playerB.publicKey, LAMPORTS_PER_SOL * 5);
+      await connection.confirmTransaction(airdrop1);
+      await connection.confirmTransaction(airdrop2);
+      code = "flagfall";
+
+      [matchAccount, bump] = PublicKey.findProgramAddressSync(
+        [Buffer.from("match"), seed.toArrayLike(Buffer, "le", 8), Buffer.from(code), playerA.publicKey.toBuffer()],
+        program.programId
+      );
+      [vault, vaultBump] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), matchAccount.toBuffer()],
+        program.programId
+      );
+      [gameLog] = PublicKey.findProgramAddressSync(
+        [Buffer.from("game_log"), matchAccount.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .setTimeControlBounds(1, 3 * 60 * 60, 180, 180)
+        .accountsPartial({ authority: authority.publicKey, config })
+        .signers([authority])
+        .rpc();
+
+      timeControl = { baseSeconds: 2, incrementSeconds: 0, delaySeconds: 0 };
+      await program.methods
+        .initializeMatch(seed, code, timeControl, { standard: {} }, colorCommitment, betAmount, null, null, null)
+        .accountsPartial({ stats, playerA: playerA.publicKey, payer: playerA.publicKey, matchAccount, vault })
+        .signers([playerA])
+        .rpc();
+      await program.methods
+        .acceptMatch(code, { standard: {} }, playerBRandom, null)
+        .accountsPartial({ stats, playerB: playerB.publicKey, playerA: playerA.publicKey, matchAccount, vault })
+        .signers([playerB])
+        .rpc();
+      await program.methods
+        .revealColors(code, Array.from(colorSecret))
+        .accountsPartial({ player: playerA.publicKey, matchAccount })
+        .signers([playerA])
+        .rpc();
+      await program.methods
+        .startGame(code)
+        .accountsPartial({ player: playerA.publicKey, matchAccount, gameLog })
+        .signers([playerA])
+        .rpc();
+    });
+
+    after(async () => {
+      await program.methods
+        .setTimeControlBounds(15, 3 * 60 * 60, 180, 180)
+        .accountsPartial({ authority: authority.publicKey, config })
+        .signers([authority])
+        .rpc();
+    });
+
+    it("Black claims once White's clock runs out", async () => {
+      const matchData = await program.account.matchState.fetch(matchAccount);
+      const black = matchData.white.equals(playerA.publicKey) ? playerB : playerA;
+      const claimFlag = () =>
+        program.methods
+          .claimFlag(code)
+          .accountsPartial({
+            stats,
+            claimant: black.publicKey,
+            playerA: playerA.publicKey,
+            playerB: playerB.publicKey,
+            matchAccount,
+            vault,
+            gameLog,
+            config,
+            treasuryPda,
+          })
+          .signers([black])
+          .rpc();
+
+      const logData = await program.account.gameLog.fetch(gameLog);
+      assert.equal(logData.whiteRemaining.toNumber(), 2);
+      assert.equal(logData.blackRemaining.toNumber(), 2);
+
+      await new Promise((resolve) => setTimeout(resolve, 4000));
+      const initialBalanceBlack = await connection.getBalance(black.publicKey);
+      await claimFlag();
+
+      const finalLog = await program.account.gameLog.fetch(gameLog);
+      assert.ok("timeout" in finalLog.status);
+      assert.ok(finalLog.winner.equals(black.publicKey));
+
+      const totalBetAmount = betAmount.mul(new anchor.BN(2));
+      const winningAmount = totalBetAmount.sub(totalBetAmount.div(new anchor.BN(200)));
+      assert.equal(await connection.getBalance(black.publicKey), initialBalanceBlack + winningAmount.toNumber());
+      assert.equal(await connection.getAccountInfo(matchAccount), null, "Match should be closed");
+    });
+  });
+
+  describe("A game played off chain and settled through a state channel", () => {
+    let channelClaim: PublicKey;
+
+    // borsh layout of ChannelState: match, nonce, board hash, Option<SideOutcome>, ResultReason (0 = checkmate)
+    const channelMessage = (nonce: number, result: number | null) => {
+      const nonceBuffer = new anchor.BN(nonce).toArrayLike(Buffer, "le", 8);
+      const resultBuffer = result === null ? Buffer.from([0]) : Buffer.from([1, result]);
+      return Buffer.concat([matchAccount.toBuffer(), nonceBuffer, Buffer.alloc(32, nonce), resultBuffer, Buffer.from([0])]);
+    };
+    const channelState = (nonce: number, result: object | null) => ({
+      matchAccount,
+      nonce: new anchor.BN(nonce),
+      boardHash: Array.from(Buffer.alloc(32, nonce)),
+      result,
+      reason: { checkmate: {} },
+    });
+    const signedByBoth = (message: Buffer) => [
+      Ed25519Program.createInstructionWithPrivateKey({ privateKey: playerA.secretKey, message }),
+      Ed25519Program.createInstructionWithPrivateKey({ privateKey: playerB.secretKey, message }),
+    ];
+
+    before(async () => {
+      seed = new anchor.BN(12);
+      betAmount = new anchor.BN(LAMPORTS_PER_SOL * 1);
+      playerA = Keypair.generate();
+      playerB = Keypair.generate();
+
+      let airdrop1 = await connection.requestAirdrop(playerA.publicKey, LAMPORTS_PER_SOL * 5);
+      let airdrop2 = await connection.requestAirdrop(playerB.publicKey, LAMPORTS_PER_SOL * 5);
+      await connection.confirmTransaction(airdrop1);
+      await connection.confirmTransaction(airdrop2);
+      code = "channel";
+
+      [matchAccount, bump] = PublicKey.findProgramAddressSync(
+        [Buffer.from("match"), seed.toArrayLike(Buffer, "le", 8), Buffer.from(code), playerA.publicKey.toBuffer()],
+        program.programId
+      );
+      [vault, vaultBump] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), matchAccount.toBuffer()],
+        program.programId
+      );
+      [channelClaim] = PublicKey.findProgramAddressSync(
+        [Buffer.from("channel"), matchAccount.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .setChallengeWindow(new anchor.BN(3))
+        .accountsPartial({ authority: authority.publicKey, config })
+        .signers([authority])
+        .rpc();
+
+      timeControl = { baseSeconds: 300, incrementSeconds: 0, delaySeconds: 0 };
+      await program.methods
+        .initializeMatch(seed, code, timeControl, { standard: {} }, colorCommitment, betAmount, null, null, null)
+        .accountsPartial({ stats, playerA: playerA.publicKey, payer: playerA.publicKey, matchAccount, vault })
+        .signers([playerA])
+        .rpc();
+      await program.methods
+        .acceptMatch(code, { standard: {} }, playerBRandom, null)
+        .accountsPartial({ stats, playerB: playerB.publicKey, playerA: playerA.publicKey, matchAccount, vault })
+        .signers([playerB])
+        .rpc();
+    });
+
+    after(async () => {
+      await program.methods
+        .setChallengeWindow(new anchor.BN(600))
+        .accountsPartial({ authority: authority.publicKey, config })
+        .signers([authority])
+        .rpc();
+    });
+
+    it("Rejects a state only one player signed", async () => {
+      const message = channelMessage(1, null);
+      try {
+        await program.methods
+          .submitChannelState(code, channelState(1, null))
+          .accountsPartial({ submitter: playerA.publicKey, matchAccount, channelClaim, config })
+          .preInstructions([Ed25519Program.createInstructionWithPrivateKey({ privateKey: playerA.secretKey, message })])
+          .signers([playerA])
+          .rpc();
+        assert.fail("A state signed by one player should be rejected");
+      } catch (error) {
+        expect(error.message).to.include("MissingSignatureError");
+      }
+    });
+
+    it("Expires a claim left on an unfinished state and refuses to resubmit it", async () => {
+      const submit = (nonce: number) =>
+        program.methods
+          .submitChannelState(code, channelState(nonce, null))
+          .accountsPartial({ submitter: playerA.publicKey, matchAccount, channelClaim, config })
+          .preInstructions(signedByBoth(channelMessage(nonce, null)))
+          .signers([playerA])
+          .rpc();
+      const expire = () =>
+        program.methods
+          .expireChannelClaim(code)
+          .accountsPartial({ cranker: wallet.publicKey, matchAccount, channelClaim, submitter: playerA.publicKey })
+          .rpc();
+
+      await submit(1);
+      assert.ok("channelPending" in (await program.account.matchState.fetch(matchAccount)).status);
+
+      try {
+        await expire();
+        assert.fail("Expiring inside the challenge window should fail");
+      } catch (error) {
+        expect(error.message).to.include("ChallengeWindowOpenError");
+      }
+
+      await new Promise((resolve) => setTimeout(resolve, 4000));
+      await expire();
+
+      const matchData = await program.account.matchState.fetch(matchAccount);
+      assert.ok("inProgress" in matchData.status);
+      assert.equal(matchData.channelNonce.toNumber(), 1);
+      assert.equal(await connection.getAccountInfo(channelClaim), null, "Channel claim should be closed");
+
+      try {
+        await submit(1);
+        assert.fail("The expired state should not be accepted again");
+      } catch (error) {
+        expect(error.message).to.include("StaleChannelStateError");
+      }
+    });
+
+    it("Submit and challenge with a newer state", async () => {
+      await program.methods
+        .submitChannelState(code, channelState(2, null))
+        .accountsPartial({ submitter: playerA.publicKey, matchAccount, channelClaim, config })
+        .preInstructions(signedByBoth(channelMessage(2, null)))
+        .signers([playerA])
+        .rpc();
+
+      await program.methods
+        .challengeChannelState(code, channelState(3, { playerB: {} }))
+        .accountsPartial({ challenger: playerB.publicKey, matchAccount, channelClaim })
+        .preInstructions(signedByBoth(channelMessage(3, 1)))
+        .signers([playerB])
+        .rpc();
+
+      try {
+        await program.methods
+          .cancelMatch(code)
+          .accountsPartial({ stats, player: playerA.publicKey, playerA: playerA.publicKey, playerB: playerB.publicKey, matchAccount, vault, config, treasuryPda })
+          .signers([playerA])
+          .rpc();
+        assert.fail("A match with a pending channel claim should not be cancellable");
+      } catch (error) {
+        expect(error.message).to.include("InvalidMatchError");
+      }
+
+      const claimData = await program.account.channelClaim.fetch(channelClaim);
+      assert.equal(claimData.nonce.toNumber(), 3);
+      assert.ok("playerB" in claimData.result);
+    });
+
+    it("Settle Channel after the challenge window", async () => {
+      const settle = () =>
+        program.methods
+          .settleChannel(code)
+          .accountsPartial({
+            stats,
+            cranker: wallet.publicKey,
+            playerA: playerA.publicKey,
+            playerB: playerB.publicKey,
+            matchAccount,
+            vault,
+            channelClaim,
+            submitter: playerA.publicKey,
+            config,
+            treasuryPda,
+          })
+          .rpc();
+
+      try {
+        await settle();
+        assert.fail("Settling inside the challenge window should fail");
+      } catch (error) {
+        expect(error.message).to.include("ChallengeWindowOpenError");
+      }
+
+      await new Promise((resolve) => setTimeout(resolve, 4000));
+      const initialBalancePlayerB = await connection.getBalance(playerB.publicKey);
+      await settle();
+
+      const totalBetAmount = betAmount.mul(new anchor.BN(2));
+      const winningAmount = totalBetAmount.sub(totalBetAmount.div(new anchor.BN(200)));
+      assert.equal(await connection.getBalance(playerB.publicKey), initialBalancePlayerB + winningAmount.toNumber());
+      assert.equal(await connection.getAccountInfo(channelClaim), null, "Channel claim should be closed");
+    });
+  });
+
+  describe("A result attested by an oracle and relayed by a player", () => {
+    const oracle = Keypair.generate();
+    const [oracleBond] = PublicKey.findProgramAddressSync(
+      [Buffer.from("oracle_bond"), oracle.publicKey.toBuffer()],
+      program.programId
+    );
+    const [oracleBondVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("oracle_bond_vault"), oracleBond.toBuffer()],
+      program.programId
+    );
+    const [oracleRole] = PublicKey.findProgramAddressSync(
+      [Buffer.from("role"), Buffer.from([1]), oracle.publicKey.toBuffer()],
+      program.programId
+    );
+
+    // borsh layout of Attestation: match, Option<winner>, reason, nonce
+    const attestationMessage = (winnerKey: PublicKey, reason: number, nonce: anchor.BN) =>
+      Buffer.concat([
+        matchAccount.toBuffer(),
+        Buffer.from([1]),
+        winnerKey.toBuffer(),
+        Buffer.from([reason]),
+        nonce.toArrayLike(Buffer, "le", 8),
+      ]);
+
+    const settleWithAttestation = (signer: Keypair, nonce: anchor.BN) =>
+      program.methods
+        .settleWithAttestation(code, oracle.publicKey, {
+          matchAccount,
+          winner: playerA.publicKey,
+          reason: { resignation: {} },
+          nonce,
+        })
+        .accountsPartial({
+          stats,
+          relayer: playerA.publicKey,
+          playerA: playerA.publicKey,
+          playerB: playerB.publicKey,
+          matchAccount,
+          vault,
+          oracleBond,
+          oracleRole,
+          config,
+          treasuryPda,
+        })
+        .preInstructions([
+          Ed25519Program.createInstructionWithPrivateKey({
+            privateKey: signer.secretKey,
+            message: attestationMessage(playerA.publicKey, 1, nonce),
+          }),
+        ])
+        .signers([playerA])
+        .rpc();
+
+    before(async () => {
+      seed = new anchor.BN(13);
+      betAmount = new anchor.BN(LAMPORTS_PER_SOL * 1);
+      playerA = Keypair.generate();
+      playerB = Keypair.generate();
+
+      let airdrop1 = await connection.requestAirdrop(playerA.publicKey, LAMPORTS_PER_SOL * 5);
+      let airdrop2 = await connection.requestAirdrop(playerB.publicKey, LAMPORTS_PER_SOL * 5);
+      await connection.confirmTransaction(airdrop1);
+      await connection.confirmTransaction(airdrop2);
+      code = "attested";
+
+      [matchAccount, bump] = PublicKey.findProgramAddressSync(
+        [Buffer.from("match"), seed.toArrayLike(Buffer, "le", 8), Buffer.from(code), playerA.publicKey.toBuffer()],
+        program.programId
+      );
+      [vault, vaultBump] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), matchAccount.toBuffer()],
+        program.programId
+      );
+
+      timeControl = { baseSeconds: 300, incrementSeconds: 0, delaySeconds: 0 };
+      await program.methods
+        .initializeMatch(seed, code, timeControl, { standard: {} }, colorCommitment, betAmount, null, null, null)
+        .accountsPartial({ stats, playerA: playerA.publicKey, payer: playerA.publicKey, matchAccount, vault })
+        .signers([playerA])
+        .rpc();
+      await program.methods
+        .acceptMatch(code, { standard: {} }, playerBRandom, null)
+        .accountsPartial({ stats, playerB: playerB.publicKey, playerA: playerA.publicKey, matchAccount, vault })
+        .signers([playerB])
+        .rpc();
+    });
+
+    it("Grant the oracle role", async () => {
+      await program.methods
+        .grantRole({ oracle: {} }, oracle.publicKey)
+        .accountsPartial({ authority: authority.publicKey, config, granted: oracleRole })
+        .signers([authority])
+        .rpc();
+      const roleData = await program.account.roleAssignment.fetch(oracleRole);
+      assert.ok(roleData.holder.equals(oracle.publicKey));
+      assert.deepEqual(roleData.role, { oracle: {} });
+    });
+
+    it("Bond Oracle", async () => {
+      const airdrop = await connection.requestAirdrop(oracle.publicKey, LAMPORTS_PER_SOL * 3);
+      await connection.confirmTransaction(airdrop);
+
+      try {
+        await settleWithAttestation(oracle, (await program.account.matchState.fetch(matchAccount)).settlementNonce);
+        assert.fail("An unbonded oracle can't settle");
+      } catch (error) {
+        expect(error.message).to.include("OracleNotBondedError");
+      }
+
+      await program.methods
+        .bondOracle(new anchor.BN(LAMPORTS_PER_SOL))
+        .accountsPartial({ oracle: oracle.publicKey, oracleBond, bondVault: oracleBondVault, config, roleAssignment: oracleRole })
+        .signers([oracle])
+        .rpc();
+      const bondData = await program.account.oracleBond.fetch(oracleBond);
+      assert.equal(bondData.amount.toNumber(), LAMPORTS_PER_SOL);
+    });
+
+    it("Rejects an attestation with the wrong nonce or signer", async () => {
+      const matchData = await program.account.matchState.fetch(matchAccount);
+      try {
+        await settleWithAttestation(oracle, matchData.settlementNonce.addn(1));
+        assert.fail("A stale nonce should be rejected");
+      } catch (error) {
+        expect(error.message).to.include("InvalidNonceError");
+      }
+      try {
+        await settleWithAttestation(playerA, matchData.settlementNonce);
+        assert.fail("Only the oracle's signature counts");
+      } catch (error) {
+        expect(error.message).to.include("MissingSignatureError");
+      }
+    });
+
+    it("Oracles only settle through final_payouts while the admin allows instant settlement", async () => {
+      try {
+        await program.methods
+          .finalPayouts(code, playerA.publicKey, { resignation: {} })
+          .accountsPartial({
+            stats,
+            authority: oracle.publicKey,
+            playerA: playerA.publicKey,
+            playerB: playerB.publicKey,
+            matchAccount,
+            vault,
+            treasuryPda,
+            config,
+            oracleBond,
+            oracleRole,
+          })
+          .signers([oracle])
+          .rpc();
+        assert.fail("An oracle can't settle directly while instant settlement is off");
+      } catch (error) {
+        expect(error.message).to.include("InstantSettlementError");
+      }
+    });
+
+    it("Settle With Attestation", async () => {
+      const matchData = await program.account.matchState.fetch(matchAccount);
+      const initialBalancePlayerB = await connection.getBalance(playerB.publicKey);
+      const initialVault = await connection.getBalance(vault);
+
+      // the oracle's signature is the authorization, instant settlement is still off
+      await settleWithAttestation(oracle, matchData.settlementNonce);
+
+      assert.equal(await connection.getBalance(playerB.publicKey), initialBalancePlayerB, "Loser gets nothing");
+      assert.equal(initialVault, betAmount.toNumber() * 2);
+      assert.equal(await connection.getAccountInfo(matchAccount), null, "Match should be closed");
+    });
+
+    it("Slash Oracle", async () => {
+      const initialBalancePlayerB = await connection.getBalance(playerB.publicKey);
+      await program.methods
+        .slashOracle(new anchor.BN(LAMPORTS_PER_SOL / 4))
+        .accountsPartial({ authority: authority.publicKey, config, oracleBond, bondVault: oracleBondVault, recipient: playerB.publicKey })
+        .signers([authority])
+        .rpc();
+      const bondData = await program.account.oracleBond.fetch(oracleBond);
+      assert.equal(bondData.amount.toNumber(), (LAMPORTS_PER_SOL * 3) / 4);
+      assert.equal(await connection.getBalance(playerB.publicKey), initialBalancePlayerB + LAMPORTS_PER_SOL / 4);
+    });
+
+    it("Request Unbond and withdraw after the cooldown", async () => {
+      await program.methods
+        .requestUnbond()
+        .accountsPartial({ oracle: oracle.publicKey, oracleBond })
+        .signers([oracle])
+        .rpc();
+
+      const withdraw = () =>
+        program.methods
+          .withdrawOracleBond()
+          .accountsPartial({ oracle: oracle.publicKey, oracleBond, bondVault: oracleBondVault, config })
+          .signers([oracle])
+          .rpc();
+
+      try {
+        await withdraw();
+        assert.fail("The bond is locked during the cooldown");
+      } catch (error) {
+        expect(error.message).to.include("UnbondCooldownError");
+      }
+
+      await program.methods
+        .setOracleBondParams(new anchor.BN(LAMPORTS_PER_SOL), new anchor.BN(0), 2500)
+        .accountsPartial({ authority: authority.publicKey, config })
+        .signers([authority])
+        .rpc();
+      await withdraw();
+      await program.methods
+        .setOracleBondParams(new anchor.BN(LAMPORTS_PER_SOL), new anchor.BN(7 * 24 * 60 * 60), 2500)
+        .accountsPartial({ authority: authority.publicKey, config })
+        .signers([authority])
+        .rpc();
+
+      assert.equal(await connection.getAccountInfo(oracleBond), null, "Bond should be closed");
+      assert.equal(await connection.getBalance(oracleBondVault), 0, "Bond vault should be empty");
+    });
+
+    it("Revoke the oracle role", async () => {
+      await program.methods
+        .revokeRole()
+        .accountsPartial({ authority: authority.publicKey, config, revoked: oracleRole, grantedBy: authority.publicKey })
+        .signers([authority])
+        .rpc();
+      assert.equal(await connection.getAccountInfo(oracleRole), null, "Role should be closed");
+    });
+  });
+
+  describe("Players report the result themselves", () => {
+    const startMatch = async (matchSeed: number, matchCode: string) => {
+      seed = new anchor.BN(matchSeed);
+      betAmount = new anchor.BN(LAMPORTS_PER_SOL * 1);
+      playerA = Keypair.generate();
+      playerB = Keypair.generate();
+
+      let airdrop1 = await connection.requestAirdrop(playerA.publicKey, LAMPORTS_PER_SOL * 5);
+      let airdrop2 = await connection.requestAirdrop(playerB.publicKey, LAMPORTS_PER_SOL * 5);
+      await connection.confirmTransaction(airdrop1);
+      await connection.confirmTransaction(airdrop2);
+      code = matchCode;
+
+      [matchAccount, bump] = PublicKey.findProgramAddressSync(
+        [Buffer.from("match"), seed.toArrayLike(Buffer, "le", 8), Buffer.from(code), playerA.publicKey.toBuffer()],
+        program.programId
+      );
+      [vault, vaultBump] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), matchAccount.toBuffer()],
+        program.programId
+      );
+
+      timeControl = { baseSeconds: 300, incrementSeconds: 0, delaySeconds: 0 };
+      await program.methods
+        .initializeMatch(seed, code, timeControl, { standard: {} }, colorCommitment, betAmount, null, null, null)
+        .accountsPartial({ stats, playerA: playerA.publicKey, payer: playerA.publicKey, matchAccount, vault })
+        .signers([playerA])
+        .rpc();
+      await program.methods
+        .acceptMatch(code, { standard: {} }, playerBRandom, null)
+        .accountsPartial({ stats, playerB: playerB.publicKey, playerA: playerA.publicKey, matchAccount, vault })
+        .signers([playerB])
+        .rpc();
+    };
+
+    const report = (reporter: Keypair, outcome: object) =>
+      program.methods
+        .reportResult(code, { outcome, reason: { resignation: {} } })
+        .accountsPartial({
+          stats,
+          reporter: reporter.publicKey,
+          playerA: playerA.publicKey,
+          playerB: playerB.publicKey,
+          matchAccount,
+          vault,
+          config,
+          treasuryPda,
+        })
+        .signers([reporter])
+        .rpc();
+
+    it("Agreeing reports settle the match", async () => {
+      await startMatch(14, "agreed");
+      await report(playerA, { playerB: {} });
+      const matchData = await program.account.matchState.fetch(matchAccount);
+      assert.ok("playerB" in matchData.reportedA.outcome);
+      assert.ok("inProgress" in matchData.status);
+
+      const initialBalancePlayerB = await connection.getBalance(playerB.publicKey);
+      let settled;
+      const listener = program.addEventListener("matchSettled", (event) => {
+        settled = event;
+      });
+      await report(playerB, { playerB: {} });
+      await new Promise((resolve) => setTimeout(resolve, 1000));
+      await program.removeEventListener(listener);
+
+      assert.ok(settled.matchAccount.equals(matchAccount));
+      assert.ok(settled.winner.equals(playerB.publicKey));
+      assert.ok("resignation" in settled.reason);
+
+      const totalBetAmount = betAmount.mul(new anchor.BN(2));
+      const winningAmount = totalBetAmount.sub(totalBetAmount.div(new anchor.BN(200)));
+      const fee = 5000; // player B signs the settling report
+      assert.equal(await connection.getBalance(playerB.publicKey), initialBalancePlayerB + winningAmount.toNumber() - fee);
+      assert.equal(await connection.getAccountInfo(matchAccount), null, "Match should be closed");
+    });
+
+    it("Conflicting reports leave the match disputed for the admin", async () => {
+      await startMatch(15, "conflict");
+      await report(playerA, { playerA: {} });
+      await report(playerB, { playerB: {} });
+
+      const matchData = await program.account.matchState.fetch(matchAccount);
+      assert.ok("disputed" in matchData.status);
+
+      const finalPayouts = (signer: Keypair) =>
+        program.methods
+          .finalPayouts(code, playerA.publicKey, { adjudication: {} })
+          .accountsPartial({
+            stats,
+            authority: signer.publicKey,
+            playerA: playerA.publicKey,
+            playerB: playerB.publicKey,
+            matchAccount,
+            vault,
+            treasuryPda,
+            config,
+          })
+          .signers([signer])
+          .rpc();
+
+      try {
+        await finalPayouts(playerA);
+        assert.fail("A player can't resolve their own dispute");
+      } catch (error) {
+        expect(error.message).to.include("InvalidAdminError");
+      }
+
+      await finalPayouts(authority);
+      assert.equal(await connection.getAccountInfo(matchAccount), null, "Match should be closed");
+    });
+  });
+
+  describe("A proposed result with a dispute window", () => {
+    let proposal: PublicKey;
+    let bondVault: PublicKey;
+
+    const startMatch = async (matchSeed: number, matchCode: string) => {
+      seed = new anchor.BN(matchSeed);
+      betAmount = new anchor.BN(LAMPORTS_PER_SOL * 1);
+      playerA = Keypair.generate();
+      playerB = Keypair.generate();
+
+      let airdrop1 = await connection.requestAirdrop(playerA.publicKey, LAMPORTS_PER_SOL * 5);
+      let airdrop2 = await connection.requestAirdrop(playerB.publicKey, LAMPORTS_PER_SOL * 5);
+      await connection.confirmTransaction(airdrop1);
+      await connection.confirmTransaction(airdrop2);
+      code = matchCode;
+
+      [matchAccount, bump] = PublicKey.findProgramAddressSync(
+        [Buffer.from("match"), seed.toArrayLike(Buffer, "le", 8), Buffer.from(code), playerA.publicKey.toBuffer()],
+        program.programId
+      );
+      [vault, vaultBump] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), matchAccount.toBuffer()],
+        program.programId
+      );
+      [proposal] = PublicKey.findProgramAddressSync(
+        [Buffer.from("proposal"), matchAccount.toBuffer()],
+        program.programId
+      );
+      [bondVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("bond_vault"), proposal.toBuffer()],
+        program.programId
+      );
+
+      timeControl = { baseSeconds: 300, incrementSeconds: 0, delaySeconds: 0 };
+      await program.methods
+        .initializeMatch(seed, code, timeControl, { standard: {} }, colorCommitment, betAmount, null, null, null)
+        .accountsPartial({ stats, playerA: playerA.publicKey, payer: playerA.publicKey, matchAccount, vault })
+        .signers([playerA])
+        .rpc();
+      await program.methods
+        .acceptMatch(code, { standard: {} }, playerBRandom, null)
+        .accountsPartial({ stats, playerB: playerB.publicKey, playerA: playerA.publicKey, matchAccount, vault })
+        .signers([playerB])
+        .rpc();
+      await program.methods
+        .proposeResult(code, playerA.publicKey, { checkmate: {} })
+        .accountsPartial({ proposer: authority.publicKey, matchAccount, proposal, bondVault, config })
+        .signers([authority])
+        .rpc();
+    };
+
+    before(async () => {
+      await program.methods
+        .setDisputeParams(new anchor.BN(2), new anchor.BN(LAMPORTS_PER_SOL / 10))
+        .accountsPartial({ authority: authority.publicKey, config })
+        .signers([authority])
+        .rpc();
+    });
+
+    after(async () => {
+      await program.methods
+        .setDisputeParams(new anchor.BN(60 * 60), new anchor.BN(LAMPORTS_PER_SOL / 10))
+        .accountsPartial({ authority: authority.publicKey, config })
+        .signers([authority])
+        .rpc();
+    });
+
+    it("Finalize Result once the window passes undisputed", async () => {
+      await startMatch(16, "proposed");
+      const matchData = await program.account.matchState.fetch(matchAccount);
+      assert.ok("proposed" in matchData.status);
+
+      const finalize = () =>
+        program.methods
+          .finalizeResult(code)
+          .accountsPartial({
+            stats,
+            cranker: wallet.publicKey,
+            playerA: playerA.publicKey,
+            playerB: playerB.publicKey,
+            matchAccount,
+            vault,
+            proposal,
+            proposer: authority.publicKey,
+            config,
+            treasuryPda,
+          })
+          .rpc();
+
+      try {
+        await finalize();
+        assert.fail("Finalizing inside the window should fail");
+      } catch (error) {
+        expect(error.message).to.include("ChallengeWindowOpenError");
+      }
+
+      await new Promise((resolve) => setTimeout(resolve, 3000));
+      const initialBalancePlayerA = await connection.getBalance(playerA.publicKey);
+      const matchRent = await connection.getBalance(matchAccount);
+      await finalize();
+
+      const totalBetAmount = betAmount.mul(new anchor.BN(2));
+      const winningAmount = totalBetAmount.sub(totalBetAmount.div(new anchor.BN(200)));
+      assert.equal(await connection.getBalance(playerA.publicKey), initialBalancePlayerA + winningAmount.toNumber() + matchRent);
+      assert.equal(await connection.getAccountInfo(proposal), null, "Proposal should be closed");
+    });
+
+    it("Dispute Result and Resolve Dispute", async () => {
+      await startMatch(17, "disputed");
+      await program.methods
+        .disputeResult(code)
+        .accountsPartial({ disputer: playerB.publicKey, matchAccount, proposal, bondVault, config })
+        .signers([playerB])
+        .rpc();
+      assert.equal(await connection.getBalance(bondVault), LAMPORTS_PER_SOL / 10);
+
+      await new Promise((resolve) => setTimeout(resolve, 3000));
+      const initialBalancePlayerB = await connection.getBalance(playerB.publicKey);
+      await program.methods
+        .resolveDispute(code, playerB.publicKey, { adjudication: {} }, false)
+        .accountsPartial({
+          stats,
+          authority: authority.publicKey,
+          playerA: playerA.publicKey,
+          playerB: playerB.publicKey,
+          matchAccount,
+          vault,
+          proposal,
+          proposer: authority.publicKey,
+          bondVault,
+          disputer: playerB.publicKey,
+          config,
+          treasuryPda,
+        })
+        .signers([authority])
+        .rpc();
+
+      const totalBetAmount = betAmount.mul(new anchor.BN(2));
+      const winningAmount = totalBetAmount.sub(totalBetAmount.div(new anchor.BN(200)));
+      assert.equal(
+        await connection.getBalance(playerB.publicKey),
+        initialBalancePlayerB + winningAmount.toNumber() + LAMPORTS_PER_SOL / 10,
+        "The winning disputer gets the pot and the bond back"
+      );
+      assert.equal(await connection.getAccountInfo(matchAccount), null, "Match should be closed");
+    });
+
+    it("A slashed dispute bond goes to the player the dispute held up", async () => {
+      await startMatch(22, "slashed");
+      await program.methods
+        .disputeResult(code)
+        .accountsPartial({ disputer: playerB.publicKey, matchAccount, proposal, bondVault, config })
+        .signers([playerB])
+        .rpc();
+
+      await new Promise((resolve) => setTimeout(resolve, 3000));
+      const initialBalancePlayerA = await connection.getBalance(playerA.publicKey);
+      const initialTreasury = await connection.getBalance(treasuryPda);
+      const matchRent = await connection.getBalance(matchAccount);
+      await program.methods
+        .resolveDispute(code, playerA.publicKey, { adjudication: {} }, true)
+        .accountsPartial({
+          stats,
+          authority: authority.publicKey,
+          playerA: playerA.publicKey,
+          playerB: playerB.publicKey,
+          matchAccount,
+          vault,
+          proposal,
+          proposer: authority.publicKey,
+          bondVault,
+          disputer: playerB.publicKey,
+          config,
+          treasuryPda,
+        })
+        .signers([authority])
+        .rpc();
+
+      const totalBetAmount = betAmount.mul(new anchor.BN(2));
+      const fee = totalBetAmount.div(new anchor.BN(200));
+      assert.equal(
+        await connection.getBalance(playerA.publicKey),
+        initialBalancePlayerA + totalBetAmount.sub(fee).toNumber() + LAMPORTS_PER_SOL / 10 + matchRent,
+        "Player A gets the pot, the slashed bond and the match rent"
+      );
+      assert.equal(await connection.getBalance(treasuryPda), initialTreasury + fee.toNumber(), "The treasury only keeps the fee");
+    });
+  });
+
+  describe("An escalated dispute decided by the arbiter panel", () => {
+    const arbiters = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+    const arbiterFee = new anchor.BN(1_000_000);
+    let arbiterSet: PublicKey;
+    let proposal: PublicKey;
+    let bondVault: PublicKey;
+    let disputeCase: PublicKey;
+
+    before(async () => {
+      seed = new anchor.BN(18);
+      betAmount = new anchor.BN(LAMPORTS_PER_SOL * 1);
+      playerA = Keypair.generate();
+      playerB = Keypair.generate();
+
+      for (const keypair of [playerA, playerB, ...arbiters]) {
+        const airdrop = await connection.requestAirdrop(keypair.publicKey, LAMPORTS_PER_SOL * 5);
+        await connection.confirmTransaction(airdrop);
+      }
+      code = "arbitrated";
+
+      [matchAccount, bump] = PublicKey.findProgramAddressSync(
+        [Buffer.from("match"), seed.toArrayLike(Buffer, "le", 8), Buffer.from(code), playerA.publicKey.toBuffer()],
+        program.programId
+      );
+      [vault, vaultBump] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), matchAccount.toBuffer()],
+        program.programId
+      );
+      [arbiterSet] = PublicKey.findProgramAddressSync([Buffer.from("arbiter_set")], program.programId);
+      [proposal] = PublicKey.findProgramAddressSync([Buffer.from("proposal"), matchAccount.toBuffer()], program.programId);
+      [bondVault] = PublicKey.findProgramAddressSync([Buffer.from("bond_vault"), proposal.toBuffer()], program.programId);
+      [disputeCase] = PublicKey.findProgramAddressSync([Buffer.from("dispute_case"), proposal.toBuffer()], program.programId);
+
+      timeControl = { baseSeconds: 300, incrementSeconds: 0, delaySeconds: 0 };
+      await program.methods
+        .initializeMatch(seed, code, timeControl, { standard: {} }, colorCommitment, betAmount, null, null, null)
+        .accountsPartial({ stats, playerA: playerA.publicKey, payer: playerA.publicKey, matchAccount, vault })
+        .signers([playerA])
+        .rpc();
+      await program.methods
+        .acceptMatch(code, { standard: {} }, playerBRandom, null)
+        .accountsPartial({ stats, playerB: playerB.publicKey, playerA: playerA.publicKey, matchAccount, vault })
+        .signers([playerB])
+        .rpc();
+      await program.methods
+        .proposeResult(code, playerA.publicKey, { checkmate: {} })
+        .accountsPartial({ proposer: authority.publicKey, matchAccount, proposal, bondVault, config })
+        .signers([authority])
+        .rpc();
+      await program.methods
+        .disputeResult(code)
+        .accountsPartial({ disputer: playerB.publicKey, matchAccount, proposal, bondVault, config })
+        .signers([playerB])
+        .rpc();
+    });
+
+    it("Create Arbiter Set", async () => {
+      await program.methods
+        .createArbiterSet(arbiters.map((a) => a.publicKey), 2, arbiterFee, new anchor.BN(60 * 60))
+        .accountsPartial({ authority: authority.publicKey, config, arbiterSet })
+        .signers([authority])
+        .rpc();
+      const configData = await program.account.matchConfig.fetch(config);
+      assert.ok(configData.arbiterSet.equals(arbiterSet));
+    });
+
+    it("Only escalates when the dispute bond covers every arbiter's fee", async () => {
+      const updateFee = (fee: anchor.BN) =>
+        program.methods
+          .updateArbiterSet(arbiters.map((a) => a.publicKey), 2, fee, new anchor.BN(60 * 60))
+          .accountsPartial({ authority: authority.publicKey, config, arbiterSet, roleAssignment: null })
+          .signers([authority])
+          .rpc();
+
+      // three votes at a third of the 0.1 SOL bond leave nothing for the bond vault's rent
+      await updateFee(new anchor.BN(33_333_333));
+      try {
+        await program.methods
+          .escalateDispute(code)
+          .accountsPartial({ payer: playerB.publicKey, matchAccount, proposal, disputeCase, arbiterSet, config })
+          .signers([playerB])
+          .rpc();
+        assert.fail("The bond can't pay the whole panel");
+      } catch (error) {
+        expect(error.message).to.include("ArbiterFeeError");
+      }
+      await updateFee(arbiterFee);
+    });
+
+    it("Escalate Dispute takes the case away from the admin", async () => {
+      await program.methods
+        .escalateDispute(code)
+        .accountsPartial({ payer: playerB.publicKey, matchAccount, proposal, disputeCase, arbiterSet, config })
+        .signers([playerB])
+        .rpc();
+
+      try {
+        await program.methods
+          .resolveDispute(code, playerA.publicKey, { adjudication: {} }, true)
+          .accountsPartial({
+            stats,
+            authority: authority.publicKey,
+            playerA: playerA.publicKey,
+            playerB: playerB.publicKey,
+            matchAccount,
+            vault,
+            proposal,
+            proposer: authority.publicKey,
+            bondVault,
+            disputer: playerB.publicKey,
+            config,
+            treasuryPda,
+          })
+          .signers([authority])
+          .rpc();
+        assert.fail("The admin can't resolve an escalated dispute");
+      } catch (error) {
+        expect(error.message).to.include("DisputeEscalatedError");
+      }
+    });
+
+    it("Cast Vote until the panel reaches a majority", async () => {
+      const castVote = (arbiter: Keypair) =>
+        program.methods
+          .castVote(code, { playerB: {} })
+          .accountsPartial({
+            stats,
+            arbiter: arbiter.publicKey,
+            playerA: playerA.publicKey,
+            playerB: playerB.publicKey,
+            matchAccount,
+            vault,
+            proposal,
+            proposer: authority.publicKey,
+            bondVault,
+            disputer: playerB.publicKey,
+            disputeCase,
+            casePayer: playerB.publicKey,
+            config,
+            treasuryPda,
+          })
+          .signers([arbiter])
+          .rpc();
+
+      const initialArbiterBalance = await connection.getBalance(arbiters[0].publicKey);
+      await castVote(arbiters[0]);
+      const caseData = await program.account.disputeCase.fetch(disputeCase);
+      assert.equal(caseData.votes.length, 1);
+      assert.equal(
+        await connection.getBalance(arbiters[0].publicKey),
+        initialArbiterBalance + arbiterFee.toNumber() - 5000,
+        "Arbiter is paid for the vote"
+      );
+      assert.equal(
+        await connection.getBalance(bondVault),
+        LAMPORTS_PER_SOL / 10 - arbiterFee.toNumber(),
+        "The fee comes out of the dispute bond"
+      );
+
+      const initialBalancePlayerB = await connection.getBalance(playerB.publicKey);
+      const caseRent = await connection.getBalance(disputeCase);
+      await castVote(arbiters[1]);
+
+      const totalBetAmount = betAmount.mul(new anchor.BN(2));
+      const winningAmount = totalBetAmount.sub(totalBetAmount.div(new anchor.BN(200)));
+      assert.equal(
+        await connection.getBalance(playerB.publicKey),
+        initialBalancePlayerB + winningAmount.toNumber() + LAMPORTS_PER_SOL / 10 - 2 * arbiterFee.toNumber() + caseRent,
+        "Overturned proposal pays player B and returns the bond, less both votes' fees, and the case rent"
+      );
+      assert.equal(await connection.getAccountInfo(matchAccount), null, "Match should be closed");
+      assert.equal(await connection.getAccountInfo(disputeCase), null, "Case should be closed");
+    });
+  });
+
+  describe("Referrals", () => {
+    const referrer = Keypair.generate();
+    const [referrerRewards] = PublicKey.findProgramAddressSync(
+      [Buffer.from("referrer_rewards"), referrer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    before(async () => {
+      seed = new anchor.BN(20);
+      betAmount = new anchor.BN(LAMPORTS_PER_SOL * 1);
+      playerA = Keypair.generate();
+      playerB = Keypair.generate();
+      code = "referred";
+
+      for (const kp of [playerA, playerB, referrer]) {
+        const airdrop = await connection.requestAirdrop(kp.publicKey, LAMPORTS_PER_SOL * 5);
+        await connection.confirmTransaction(airdrop);
+      }
+
+      [matchAccount] = PublicKey.findProgramAddressSync(
+        [Buffer.from("match"), seed.toArrayLike(Buffer, "le", 8), Buffer.from(code), playerA.publicKey.toBuffer()],
+        program.programId
+      );
+      [vault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), matchAccount.toBuffer()],
+        program.programId
+      );
+    });
+
+    it("Rebates part of the fee to the player's referrer", async () => {
+      await program.methods
+        .registerReferrer()
+        .accountsPartial({ referrer: referrer.publicKey, referrerRewards })
+        .signers([referrer])
+        .rpc();
+
+      const [referral] = PublicKey.findProgramAddressSync(
+        [Buffer.from("referral"), playerA.publicKey.toBuffer()],
+        program.programId
+      );
+      timeControl = { baseSeconds: 300, incrementSeconds: 0, delaySeconds: 0 };
+      await program.methods
+        .initializeMatch(seed, code, timeControl, { standard: {} }, colorCommitment, betAmount, null, null, referrer.publicKey)
+        .accountsPartial({ stats, playerA: playerA.publicKey, payer: playerA.publicKey, matchAccount, vault, referral, referrerRewards })
+        .signers([playerA])
+        .rpc();
+      await program.methods
+        .acceptMatch(code, { standard: {} }, playerBRandom, null)
+        .accountsPartial({ stats, playerB: playerB.publicKey, playerA: playerA.publicKey, matchAccount, vault })
+        .signers([playerB])
+        .rpc();
+
+      const referralData = await program.account.referral.fetch(referral);
+      assert.ok(referralData.referrer.equals(referrer.publicKey));
+      const matchData = await program.account.matchState.fetch(matchAccount);
+      assert.ok(matchData.referrerA.equals(referrer.publicKey));
+      assert.isNull(matchData.referrerB);
+
+      const settle = (accounts: object) =>
+        program.methods
+          .finalPayouts(code, playerA.publicKey, { checkmate: {} })
+          .accountsPartial({
+            stats,
+            authority: authority.publicKey,
+            playerA: playerA.publicKey,
+            playerB: playerB.publicKey,
+            matchAccount,
+            vault,
+            treasuryPda,
+            config,
+            ...accounts,
+          })
+          .signers([authority])
+          .rpc();
+
+      try {
+        await settle({});
+        assert.fail("Settling without the referrer's rewards account should fail");
+      } catch (error) {
+        expect(error.message).to.include("InvalidReferralError");
+      }
+      await settle({ referrerRewardsA: referrerRewards });
+
+      const { referralFeeBps } = await program.account.matchConfig.fetch(config);
+      const fee = betAmount.muln(2).muln(50).divn(10_000);
+      const rebate = fee.divn(2).muln(referralFeeBps).divn(10_000);
+      const rewardsData = await program.account.referrerRewards.fetch(referrerRewards);
+      assert.equal(rewardsData.claimable.toNumber(), rebate.toNumber());
+      assert.equal(rewardsData.totalEarned.toNumber(), rebate.toNumber());
+
+      const initialReferrerBalance = await connection.getBalance(referrer.publicKey);
+      await program.methods
+        .claimReferralRewards()
+        .accountsPartial({ referrer: referrer.publicKey, referrerRewards })
+        .signers([referrer])
+        .rpc();
+      assert.equal(
+        await connection.getBalance(referrer.publicKey),
+        initialReferrerBalance + rebate.toNumber() - 5000,
+        "Referrer should receive the rebate"
+      );
+      assert.equal((await program.account.referrerRewards.fetch(referrerRewards)).claimable.toNumber(), 0);
+    });
+
+    it("Rebates on matches the players settle themselves", async () => {
+      seed = new anchor.BN(23);
+      code = "referredReport";
+      [matchAccount] = PublicKey.findProgramAddressSync(
+        [Buffer.from("match"), seed.toArrayLike(Buffer, "le", 8), Buffer.from(code), playerA.publicKey.toBuffer()],
+        program.programId
+      );
+      [vault] = PublicKey.findProgramAddressSync([Buffer.from("vault"), matchAccount.toBuffer()], program.programId);
+      const [referral] = PublicKey.findProgramAddressSync(
+        [Buffer.from("referral"), playerA.publicKey.toBuffer()],
+        program.programId
+      );
+
+      // the referral recorded on the first match carries over
+      await program.methods
+        .initializeMatch(seed, code, timeControl, { standard: {} }, colorCommitment, betAmount, null, null, null)
+        .accountsPartial({ stats, playerA: playerA.publicKey, payer: playerA.publicKey, matchAccount, vault, referral })
+        .signers([playerA])
+        .rpc();
+      await program.methods
+        .acceptMatch(code, { standard: {} }, playerBRandom, null)
+        .accountsPartial({ stats, playerB: playerB.publicKey, playerA: playerA.publicKey, matchAccount, vault })
+        .signers([playerB])
+        .rpc();
+
+      const report = (reporter: Keypair, accounts: object) =>
+        program.methods
+          .reportResult(code, { outcome: { playerA: {} }, reason: { resignation: {} } })
+          .accountsPartial({
+            stats,
+            reporter: reporter.publicKey,
+            playerA: playerA.publicKey,
+            playerB: playerB.publicKey,
+            matchAccount,
+            vault,
+            config,
+            treasuryPda,
+            ...accounts,
+          })
+          .signers([reporter])
+          .rpc();
+
+      await report(playerA, {});
+      try {
+        await report(playerB, {});
+        assert.fail("The agreeing report can't leave the referrer out");
+      } catch (error) {
+        expect(error.message).to.include("InvalidReferralError");
+      }
+      await report(playerB, { referrerRewardsA: referrerRewards });
+
+      const { referralFeeBps } = await program.account.matchConfig.fetch(config);
+      const fee = betAmount.muln(2).muln(50).divn(10_000);
+      const rebate = fee.divn(2).muln(referralFeeBps).divn(10_000);
+      assert.equal((await program.account.referrerRewards.fetch(referrerRewards)).claimable.toNumber(), rebate.toNumber());
+      assert.equal(await connection.getAccountInfo(matchAccount), null, "Match should be closed");
+    });
+  });
+
+  describe("Roles", () => {
+    const pauser = Keypair.generate();
+    const [pauserRole] = PublicKey.findProgramAddressSync(
+      [Buffer.from("role"), Buffer.from([2]), pauser.publicKey.toBuffer()],
+      program.programId
+    );
+
+    const setPaused = (signer: Keypair, paused: boolean, roleAssignment: PublicKey | null) =>
+      program.methods
+        .setPaused(paused)
+        .accountsPartial({ authority: signer.publicKey, config, roleAssignment })
+        .signers([signer])
+        .rpc();
+
+    before(async () => {
+      const airdrop = await connection.requestAirdrop(pauser.publicKey, LAMPORTS_PER_SOL);
+      await connection.confirmTransaction(airdrop);
+    });
+
+    it("A pauser can pause new matches but not resume them", async () => {
+      await program.methods
+        .grantRole({ pauser: {} }, pauser.publicKey)
+        .accountsPartial({ authority: authority.publicKey, config, granted: pauserRole })
+        .signers([authority])
+        .rpc();
+
+      try {
+        await setPaused(pauser, true, null);
+        assert.fail("Pausing without the role assignment should fail");
+      } catch (error) {
+        expect(error.message).to.include("MissingRoleError");
+      }
+      await setPaused(pauser, true, pauserRole);
+      assert.isTrue((await program.account.matchConfig.fetch(config)).paused);
+
+      const player = Keypair.generate();
+      const airdrop = await connection.requestAirdrop(player.publicKey, LAMPORTS_PER_SOL * 2);
+      await connection.confirmTransaction(airdrop);
+      const pausedSeed = new anchor.BN(19);
+      const pausedCode = "paused";
+      const [pausedMatch] = PublicKey.findProgramAddressSync(
+        [Buffer.from("match"), pausedSeed.toArrayLike(Buffer, "le", 8), Buffer.from(pausedCode), player.publicKey.toBuffer()],
+        program.programId
+      );
+      const [pausedVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), pausedMatch.toBuffer()],
+        program.programId
+      );
+      try {
+        await program.methods
+          .initializeMatch(
+            pausedSeed,
+            pausedCode,
+            { baseSeconds: 300, incrementSeconds: 0, delaySeconds: 0 },
+            { standard: {} },
+            colorCommitment,
+            new anchor.BN(LAMPORTS_PER_SOL / 10),
+            null,
+            null,
+            null
+          )
+          .accountsPartial({ stats, playerA: player.publicKey, payer: player.publicKey, matchAccount: pausedMatch, vault: pausedVault })
+          .signers([player])
+          .rpc();
+        assert.fail("Creating a match while paused should fail");
+      } catch (error) {
+        expect(error.message).to.include("PausedError");
+      }
+
+      try {
+        await setPaused(pauser, false, pauserRole);
+        assert.fail("A pauser should not be able to resume");
+      } catch (error) {
+        expect(error.message).to.include("MissingRoleError");
+      }
+      await setPaused(authority, false, null);
+      assert.isFalse((await program.account.matchConfig.fetch(config)).paused);
+
+      await program.methods
+        .revokeRole()
+        .accountsPartial({ authority: authority.publicKey, config, revoked: pauserRole, grantedBy: authority.publicKey })
+        .signers([authority])
+        .rpc();
+    });
+
+    it("Only the authority grants the admin and treasurer roles", async () => {
+      const delegate = Keypair.generate();
+      const airdrop = await connection.requestAirdrop(delegate.publicKey, LAMPORTS_PER_SOL);
+      await connection.confirmTransaction(airdrop);
+      const roleFor = (role: number, holder: PublicKey) =>
+        PublicKey.findProgramAddressSync([Buffer.from("role"), Buffer.from([role]), holder.toBuffer()], program.programId)[0];
+      const delegateAdmin = roleFor(0, delegate.publicKey);
+
+      await program.methods
+        .grantRole({ admin: {} }, delegate.publicKey)
+        .accountsPartial({ authority: authority.publicKey, config, granted: delegateAdmin })
+        .signers([authority])
+        .rpc();
+
+      for (const [role, index] of [[{ admin: {} }, 0], [{ treasurer: {} }, 3]] as [object, number][]) {
+        try {
+          await program.methods
+            .grantRole(role, pauser.publicKey)
+            .accountsPartial({ authority: delegate.publicKey, config, roleAssignment: delegateAdmin, granted: roleFor(index, pauser.publicKey) })
+            .signers([delegate])
+            .rpc();
+          assert.fail("A delegated admin can't grant admin or treasury powers");
+        } catch (error) {
+          expect(error.message).to.include("InvalidAdminError");
+        }
+      }
+
+      await program.methods
+        .revokeRole()
+        .accountsPartial({ authority: authority.publicKey, config, revoked: delegateAdmin, grantedBy: authority.publicKey })
+        .signers([authority])
+        .rpc();
+    });
+  });
+
+  describe("Fee split", () => {
+    const team = Keypair.generate();
+    const insurance = Keypair.generate();
+    const [feeSplit] = PublicKey.findProgramAddressSync([Buffer.from("fee_split")], program.programId);
+
+    it("Only pays beneficiaries through the withdrawal timelock", async () => {
+      try {
+        await program.methods
+          .createFeeSplit([{ beneficiary: team.publicKey, shareBps: 7000 }], new anchor.BN(0))
+          .accountsPartial({ authority: authority.publicKey, config, feeSplit })
+          .signers([authority])
+          .rpc();
+        assert.fail("Shares that don't add up to 100% should fail");
+      } catch (error) {
+        expect(error.message).to.include("InvalidFeeBps");
+      }
+
+      await program.methods
+        .createFeeSplit(
+          [
+            { beneficiary: team.publicKey, shareBps: 7000 },
+            { beneficiary: insurance.publicKey, shareBps: 3000 },
+          ],
+          new anchor.BN(0)
+        )
+        .accountsPartial({ authority: authority.publicKey, config, feeSplit })
+        .signers([authority])
+        .rpc();
+
+      const treasuryAccount = await connection.getAccountInfo(treasuryPda);
+      const treasuryRent = await connection.getMinimumBalanceForRentExemption(treasuryAccount.data.length);
+      assert.ok(treasuryAccount.lamports - treasuryRent > 0, "Earlier matches should have left fees in the treasury");
+
+      const distribute = () =>
+        program.methods
+          .distributeFees()
+          .accountsPartial({ stats, cranker: playerA.publicKey, config, feeSplit, treasuryPda })
+          .remainingAccounts([
+            { pubkey: team.publicKey, isSigner: false, isWritable: true },
+            { pubkey: insurance.publicKey, isSigner: false, isWritable: true },
+          ])
+          .signers([playerA])
+          .rpc();
+
+      // beneficiaries are treasury outflows like any withdrawal, allowlisted and past the delay
+      try {
+        await distribute();
+        assert.fail("Beneficiaries off the withdrawal allowlist can't be paid");
+      } catch (error) {
+        expect(error.message).to.include("InvalidDestinationError");
+      }
+      for (const beneficiary of [team, insurance]) {
+        await program.methods
+          .addWithdrawDestination(beneficiary.publicKey)
+          .accountsPartial({ authority: authority.publicKey, config })
+          .signers([authority])
+          .rpc();
+      }
+      try {
+        await distribute();
+        assert.fail("Beneficiaries can't be paid before their withdrawal delay has passed");
+      } catch (error) {
+        expect(error.message).to.include("InvalidDestinationError");
+      }
+      assert.equal(await connection.getBalance(team.publicKey), 0);
+      assert.equal(await connection.getBalance(insurance.publicKey), 0);
+    });
+  });
+
+  // hands the admin role to the signer set for good, keep this describe last
+  describe("Admin multisig", () => {
+    const signerB = Keypair.generate();
+    const signerC = Keypair.generate();
+    const [adminPda] = PublicKey.findProgramAddressSync([Buffer.from("admin")], program.programId);
+
+    const proposalPda = async () => {
+      const { proposalCount } = await program.account.matchConfig.fetch(config);
+      return PublicKey.findProgramAddressSync(
+        [Buffer.from("admin_proposal"), proposalCount.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      )[0];
+    };
+
+    const [signerBAdmin] = PublicKey.findProgramAddressSync(
+      [Buffer.from("role"), Buffer.from([0]), signerB.publicKey.toBuffer()],
+      program.programId
+    );
+    const [signerBFeeManager] = PublicKey.findProgramAddressSync(
+      [Buffer.from("role"), Buffer.from([4]), signerB.publicKey.toBuffer()],
+      program.programId
+    );
+
+    before(async () => {
+      for (const kp of [signerB, signerC]) {
+        const airdrop = await connection.requestAirdrop(kp.publicKey, LAMPORTS_PER_SOL);
+        await connection.confirmTransaction(airdrop);
+      }
+      await program.methods
+        .grantRole({ admin: {} }, signerB.publicKey)
+        .accountsPartial({ authority: authority.publicKey, config, granted: signerBAdmin })
+        .signers([authority])
+        .rpc();
+      await program.methods
+        .grantRole({ feeManager: {} }, signerB.publicKey)
+        .accountsPartial({ authority: authority.publicKey, config, granted: signerBFeeManager })
+        .signers([authority])
+        .rpc();
+    });
+
+    it("Hands the admin role to a 2-of-3 signer set", async () => {
+      await program.methods
+        .setAdminSigners([authority.publicKey, signerB.publicKey, signerC.publicKey], 2)
+        .accountsPartial({ authority: authority.publicKey, config })
+        .signers([authority])
+        .rpc();
+
+      const configData = await program.account.matchConfig.fetch(config);
+      assert.ok(configData.authority.equals(adminPda), "Authority should be the admin PDA");
+      assert.equal(configData.adminThreshold, 2);
+
+      try {
+        await program.methods
+          .setRevealWindow(new anchor.BN(900))
+          .accountsPartial({ authority: authority.publicKey, config })
+          .signers([authority])
+          .rpc();
+        assert.fail("A single signer should no longer be able to act as admin");
+      } catch (error) {
+        expect(error.message).to.include("InvalidAdminError");
+      }
+
+      try {
+        await program.methods
+          .setRevealWindow(new anchor.BN(900))
+          .accountsPartial({ authority: signerB.publicKey, config, roleAssignment: signerBAdmin })
+          .signers([signerB])
+          .rpc();
+        assert.fail("An admin role granted before the handover should no longer count");
+      } catch (error) {
+        expect(error.message).to.include("InvalidAdminError");
+      }
+
+      try {
+        await program.methods
+          .setCancelPenaltyParams(100, new anchor.BN(600), 100, 500, 7500)
+          .accountsPartial({ feeManager: signerB.publicKey, config, roleAssignment: signerBFeeManager })
+          .signers([signerB])
+          .rpc();
+        assert.fail("Fee changes should need the signer set too");
+      } catch (error) {
+        expect(error.message).to.include("MissingRoleError");
+      }
+    });
+
+    it("Executes an admin instruction once the threshold approves it", async () => {
+      const ix = await program.methods
+        .setRevealWindow(new anchor.BN(900))
+        .accountsPartial({ authority: adminPda, config })
+        .instruction();
+      const proposal = await proposalPda();
+      await program.methods
+        .createProposal(
+          ix.keys.map((key) => ({ pubkey: key.pubkey, isSigner: key.isSigner, isWritable: key.isWritable })),
+          ix.data
+        )
+        .accountsPartial({ proposer: authority.publicKey, config, proposal })
+        .signers([authority])
+        .rpc();
+
+      const execute = () =>
+        program.methods
+          .executeProposal()
+          .accountsPartial({ executor: signerC.publicKey, config, proposal, proposer: authority.publicKey })
+          .remainingAccounts(ix.keys.map((key) => ({ ...key, isSigner: false })))
+          .signers([signerC])
+          .rpc();
+
+      try {
+        await execute();
+        assert.fail("Executing with one approval should fail");
+      } catch (error) {
+        expect(error.message).to.include("ThresholdNotMetError");
+      }
+
+      await program.methods
+        .approveProposal()
+        .accountsPartial({ signer: signerB.publicKey, config, proposal })
+        .signers([signerB])
+        .rpc();
+      await execute();
+
+      const configData = await program.account.matchConfig.fetch(config);
+      assert.equal(configData.revealWindow.toNumber(), 900, "Proposal should have updated the config");
+      assert.isNull(await connection.getAccountInfo(proposal), "Executed proposal should be closed");
     });
   });
 